@@ -1,11 +1,28 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
-use anchor_spl::token::{self, Mint, TokenAccount, Token, Transfer, MintTo, InitializeMint, Burn, spl_token};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::{program::invoke_signed, system_instruction, ed25519_program};
+use anchor_lang::solana_program::sysvar::instructions::{self as sysvar_instructions, load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::token::{self, Mint, TokenAccount, Token, Transfer, MintTo, InitializeMint, Burn, CloseAccount, spl_token};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
 
 declare_id!("DYMKJAp7o44QC7ZwB6JFbJY4mkDDtoAMbrwsTCrZqcj3");
 
 pub const SCALE: u128 = 1_000_000_000u128; // 1e9 fixed-point
+pub const BPS_DENOMINATOR: u128 = 10_000;
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 500; // 5%
+pub const MAX_SPONSOR_FEE_BPS: u16 = 1_000; // 10%
+pub const MAX_FEE_EXEMPT: usize = 16;
+pub const SELL_ORDER_DOMAIN: &[u8] = b"rwa:sell_order:v1";
+pub const PRICE_OBSERVATIONS: usize = 32;
+pub const MIN_OBSERVATION_INTERVAL_SECS: i64 = 60; // Fills sooner after the newest observation don't take a slot
+pub const MAX_REWARD_MINTS: usize = 4;
+pub const MAX_RESERVE_BPS: u16 = 5_000; // 50%
+pub const MAX_MANAGEMENT_FEE_BPS: u16 = 2_000; // 20%
+pub const MAX_PERFORMANCE_FEE_BPS: u16 = 3_000; // 30%
+pub const MAX_CRANK_REWARD_BPS: u16 = 100; // 1%
+pub const MAX_WITHHOLDING_BPS: u16 = 5_000; // 50%
+pub const MAX_RECORD_DELAY_SECS: i64 = 90 * 86_400; // Latest record date a deposit may set
+pub const RENT_CHECKPOINTS: usize = 8; // Recent rent credits kept for holding period settlement
 
 #[program]
 pub mod rwa {
@@ -25,12 +42,61 @@ pub mod rwa {
         property.authority = ctx.accounts.authority.key();
         property.nft_mint = ctx.accounts.nft_mint.key();
         property.fraction_mint = ctx.accounts.fraction_mint.key();
+        property.payment_mint = ctx.accounts.usdc_mint.key();
+        property.payment_decimals = ctx.accounts.usdc_mint.decimals;
         property.total_fractions = total_fractions;
         property.minted_fractions = 0;
         property.fraction_decimal = fraction_decimal;
         property.cum_rent_per_share = 0u128;
         property.bump = 0;
         property.metadata_uri = metadata_uri;
+        property.sponsor_fee_bps = 0;
+        property.rofr_window_secs = 0;
+        property.undistributed_remainder = 0;
+        property.rent_credited = 0;
+        property.rent_paid_out = 0;
+        property.stream_period_secs = 0;
+        property.stream_pending = 0;
+        property.stream_end = 0;
+        property.stream_last_update = 0;
+        property.reward_count = 0;
+        property.rewards = [RewardStream::default(); MAX_REWARD_MINTS];
+        property.manager = ctx.accounts.authority.key();
+        property.expense_count = 0;
+        property.expenses_payable = 0;
+        property.expense_funds = 0;
+        property.total_expenses_paid = 0;
+        property.reserve_bps = 0;
+        property.reserve_cap = 0;
+        property.reserve_balance = 0;
+        property.drawdown_count = 0;
+        property.management_fee_bps = 0;
+        property.performance_fee_bps = 0;
+        property.perf_hurdle = 0;
+        property.perf_window_secs = 0;
+        property.perf_window_start = 0;
+        property.perf_window_distributed = 0;
+        property.crank_reward_bps = 0;
+        property.crank_funds = 0;
+        property.epoch_count = 0;
+        property.epoch_credited = 0;
+        property.epoch_pending = 0;
+        property.epoch_record_date = 0;
+        property.escheat_after_secs = 0;
+        property.escheat_after_epochs = 0;
+        property.total_escheated = 0;
+        property.supply_halted = false;
+        property.offering_price = 0;
+        property.cum_scrip_per_share = 0;
+        property.scrip_remainder = 0;
+        property.scrip_reserved = 0;
+        property.tracked_balance = 0;
+        property.holding_period_secs = 0;
+        property.total_rent_forfeited = 0;
+        property.cum_rent_time_per_share = 0;
+        property.rent_checkpoints = [RentCheckpoint::default(); RENT_CHECKPOINTS];
+        property.rent_checkpoint_head = 0;
+        property.rent_checkpoint_len = 0;
 
         // 1) Create fraction mint account (system create_account)
         let rent = Rent::get()?;
@@ -85,13 +151,21 @@ pub mod rwa {
         Ok(())
     }
 
-    /// Mint fractions into owner's fraction token account. Only property authority may call.
+    /// Mint fractions into owner's fraction token account and credit the owner's
+    /// holder state. Only property authority may call.
     pub fn mint_fractions(ctx: Context<MintFractions>, amount: u64) -> Result<()> {
         let property = &mut ctx.accounts.property_account;
+        // Release streamed rent to the existing supply before it changes
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
         require!(ctx.accounts.authority.key() == property.authority, ErrorCode::Unauthorized);
+        require!(property.epoch_pending == 0, ErrorCode::EpochPending);
         
-        // Update minted supply
+        // Update minted supply; fractions reserved for declared scrip are not available
         property.minted_fractions = property.minted_fractions.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+        require!(
+            property.minted_fractions.checked_add(property.scrip_reserved).ok_or(ErrorCode::NumericOverflow)? <= property.total_fractions,
+            ErrorCode::ExceedsTotalFractions
+        );
 
         // Mint fractions via CPI - mint authority is a PDA; sign with its seeds
         let (_pda, bump) = Pubkey::find_program_address(&[b"fraction_authority", property.to_account_info().key.as_ref()], ctx.program_id);
@@ -104,45 +178,115 @@ pub mod rwa {
         };
         token::mint_to(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), amount)?;
 
+        let holder = &mut ctx.accounts.destination_holder;
+        init_holder(holder, ctx.accounts.destination.owner, property, ctx.bumps.destination_holder)?;
+        settle_holder(property, holder)?;
+        add_holder_balance(holder, amount, Clock::get()?.unix_timestamp)?;
+        reset_holder_debt(property, holder)?;
+        property.tracked_balance = property.tracked_balance.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+
         Ok(())
     }
 
-    /// Buy fractions: buyer pays USDC to seller, program transfers fraction tokens
-    /// from seller to buyer. Both buyer and seller holder accounting is updated.
-    pub fn buy_fractions(ctx: Context<BuyFractions>, fraction_amount: u64, price: u64) -> Result<()> {
-        let property = &ctx.accounts.property_account;
+    /// Create the caller's holder state and sync it to their fraction ATA.
+    pub fn register_holder(ctx: Context<RegisterHolder>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
+        let holder = &mut ctx.accounts.holder_state;
+        init_holder(holder, ctx.accounts.holder.key(), property, ctx.bumps.holder_state)?;
+        mark_holder_active(property, holder)?;
+        sync_holder_balance(property, holder, ctx.accounts.holder_fraction_ata.amount)
+    }
 
-        // Transfer USDC from buyer to seller
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.buyer_usdc.to_account_info(),
-            to: ctx.accounts.seller_usdc.to_account_info(),
-            authority: ctx.accounts.buyer.to_account_info().clone(),
-        };
-        token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), price)?;
-
-        // Update seller holder accounting
-        let seller = &mut ctx.accounts.seller_holder;
-        let seller_balance = seller.balance as u128;
-        let accrued_seller = seller_balance.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
-        let pending_seller = accrued_seller.checked_sub(seller.reward_debt).unwrap_or(0u128);
-        seller.unclaimed = seller.unclaimed.checked_add(pending_seller).ok_or(ErrorCode::NumericOverflow)?;
-        
-        // Deduct balance from seller
-        seller.balance = seller.balance.checked_sub(fraction_amount).ok_or(ErrorCode::InsufficientFunds)?;
-        let new_seller_balance_u128 = seller.balance as u128;
-        seller.reward_debt = new_seller_balance_u128.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
-
-        // Update buyer holder accounting
-        let buyer = &mut ctx.accounts.buyer_holder;
-        let buyer_balance = buyer.balance as u128;
-        let accrued_buyer = buyer_balance.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
-        let pending_buyer = accrued_buyer.checked_sub(buyer.reward_debt).unwrap_or(0u128);
-        buyer.unclaimed = buyer.unclaimed.checked_add(pending_buyer).ok_or(ErrorCode::NumericOverflow)?;
-
-        // Add balance to buyer
-        buyer.balance = buyer.balance.checked_add(fraction_amount).ok_or(ErrorCode::NumericOverflow)?;
-        let new_buyer_balance_u128 = buyer.balance as u128;
-        buyer.reward_debt = new_buyer_balance_u128.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
+    /// Settle a holder and set their tracked balance to their fraction ATA balance,
+    /// e.g. after fractions moved outside the program. Increases are limited to
+    /// supply no other holder state tracks, so the sender must be synced first.
+    /// Anyone may call.
+    pub fn sync_holder(ctx: Context<SyncHolder>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
+        sync_holder_balance(property, &mut ctx.accounts.holder_state, ctx.accounts.holder_fraction_ata.amount)
+    }
+
+    /// Close an empty holder state and refund its lamports to the holder. Requires
+    /// zero balance and no claimable rent, rewards or scrip; sub-unit dust goes
+    /// back to the property remainders. The state can be re-created later.
+    pub fn close_holder_state(ctx: Context<CloseHolderState>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
+        let holder = &mut ctx.accounts.holder_state;
+        require!(holder.balance == 0, ErrorCode::HolderNotEmpty);
+
+        settle_holder(property, holder)?;
+        require!(holder.unclaimed < SCALE, ErrorCode::HolderNotEmpty);
+        require!(holder.scrip_unclaimed < SCALE, ErrorCode::HolderNotEmpty);
+        let reward_count = property.reward_count as usize;
+        require!(holder.reward_unclaimed[..reward_count].iter().all(|u| *u < SCALE), ErrorCode::HolderNotEmpty);
+
+        property.undistributed_remainder = property.undistributed_remainder
+            .checked_add(holder.unclaimed).ok_or(ErrorCode::NumericOverflow)?;
+        property.scrip_remainder = property.scrip_remainder
+            .checked_add(holder.scrip_unclaimed).ok_or(ErrorCode::NumericOverflow)?;
+        for (reward, dust) in property.rewards[..reward_count].iter_mut().zip(holder.reward_unclaimed) {
+            reward.undistributed_remainder = reward.undistributed_remainder.checked_add(dust).ok_or(ErrorCode::NumericOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    /// Buy fractions: atomic swap where the buyer pays `price_per_fraction` USDC per
+    /// whole fraction to the seller and the program moves the fraction tokens from
+    /// seller to buyer. Fails if the total cost exceeds `max_total_cost` or the
+    /// quote has passed `valid_until`. Both buyer and seller holder accounting is updated.
+    pub fn buy_fractions(
+        ctx: Context<BuyFractions>,
+        fraction_amount: u64,
+        price_per_fraction: u64,
+        max_total_cost: u64,
+        valid_until: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        accrue_rent(&mut ctx.accounts.property_account, now)?;
+        let property = &mut ctx.accounts.property_account;
+
+        require!(fraction_amount > 0, ErrorCode::InvalidAmount);
+        require!(now <= valid_until, ErrorCode::OrderExpired);
+        require_keys_neq!(ctx.accounts.buyer.key(), ctx.accounts.seller.key(), ErrorCode::SelfTrade);
+
+        require_rofr_direct_sale(property, &ctx.accounts.buyer_holder)?;
+
+        let price = trade_cost(fraction_amount, price_per_fraction, property.fraction_decimal)?;
+        require!(price <= max_total_cost, ErrorCode::SlippageExceeded);
+
+        // Split the USDC leg into protocol fee, sponsor royalty and seller proceeds
+        let fees = trade_fees(
+            &ctx.accounts.protocol_config,
+            property,
+            &ctx.accounts.buyer.key(),
+            &ctx.accounts.seller.key(),
+            price,
+        )?;
+        pay_trade_usdc(
+            &ctx.accounts.token_program,
+            ctx.accounts.buyer.as_ref(),
+            &ctx.accounts.buyer_usdc,
+            &ctx.accounts.seller_usdc,
+            &ctx.accounts.treasury_usdc,
+            &ctx.accounts.sponsor_usdc,
+            &fees,
+        )?;
+
+        // Update seller and buyer holder accounting
+        init_holder(&mut ctx.accounts.seller_holder, ctx.accounts.seller.key(), property, ctx.bumps.seller_holder)?;
+        init_holder(&mut ctx.accounts.buyer_holder, ctx.accounts.buyer.key(), property, ctx.bumps.buyer_holder)?;
+        move_holder_balance(
+            property,
+            &mut ctx.accounts.seller_holder,
+            &mut ctx.accounts.buyer_holder,
+            fraction_amount,
+        )?;
+        mark_holder_active(property, &mut ctx.accounts.seller_holder)?;
+        mark_holder_active(property, &mut ctx.accounts.buyer_holder)?;
 
         // Transfer fraction tokens from seller to buyer
         let cpi_accounts_f = Transfer {
@@ -152,38 +296,343 @@ pub mod rwa {
         };
         token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts_f), fraction_amount)?;
 
+        ctx.accounts.price_oracle.record_fill(property.key(), now, price_per_fraction, fraction_amount, price, property.payment_decimals)?;
+
+        emit!(FractionsTraded {
+            property: property.key(),
+            buyer: ctx.accounts.buyer.key(),
+            seller: ctx.accounts.seller.key(),
+            fraction_amount,
+            price_per_fraction,
+            price,
+            protocol_fee: fees.protocol_fee,
+            sponsor_fee: fees.sponsor_fee,
+        });
+
         Ok(())
     }
 
-    /// Transfer fractions between two holders. All transfers must go through
-    /// the program to correctly update reward accounting.
-    pub fn transfer_fractions(ctx: Context<TransferFractions>, amount: u64) -> Result<()> {
+    /// Fill an off-chain signed sell order. The seller signs `SELL_ORDER_DOMAIN`
+    /// followed by the borsh-encoded `SellOrder` and approves the `order_delegate`
+    /// PDA on their fraction account; any buyer submits the order preceded by an
+    /// ed25519 verify instruction. Each order nonce can only be filled once.
+    pub fn execute_signed_order(ctx: Context<ExecuteSignedOrder>, order: SellOrder, max_total_cost: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        accrue_rent(&mut ctx.accounts.property_account, now)?;
+        let property = &mut ctx.accounts.property_account;
+
+        require_keys_eq!(order.property, property.key(), ErrorCode::InvalidOrder);
+        require!(order.fraction_amount > 0, ErrorCode::InvalidAmount);
+        require!(now <= order.expiry, ErrorCode::OrderExpired);
+        require_keys_neq!(ctx.accounts.buyer.key(), order.seller, ErrorCode::SelfTrade);
+
+        // The preceding ed25519 instruction must have verified the seller's signature over this exact order
+        let mut message = SELL_ORDER_DOMAIN.to_vec();
+        order.serialize(&mut message)?;
+        verify_ed25519_signature(&ctx.accounts.instructions_sysvar, &order.seller, &message)?;
+
+        require_rofr_direct_sale(property, &ctx.accounts.buyer_holder)?;
+
+        let price = trade_cost(order.fraction_amount, order.price_per_fraction, property.fraction_decimal)?;
+        require!(price <= max_total_cost, ErrorCode::SlippageExceeded);
+
+        let fees = trade_fees(
+            &ctx.accounts.protocol_config,
+            property,
+            &ctx.accounts.buyer.key(),
+            &order.seller,
+            price,
+        )?;
+        pay_trade_usdc(
+            &ctx.accounts.token_program,
+            ctx.accounts.buyer.as_ref(),
+            &ctx.accounts.buyer_usdc,
+            &ctx.accounts.seller_usdc,
+            &ctx.accounts.treasury_usdc,
+            &ctx.accounts.sponsor_usdc,
+            &fees,
+        )?;
+
+        init_holder(&mut ctx.accounts.buyer_holder, ctx.accounts.buyer.key(), property, ctx.bumps.buyer_holder)?;
+        move_holder_balance(
+            property,
+            &mut ctx.accounts.seller_holder,
+            &mut ctx.accounts.buyer_holder,
+            order.fraction_amount,
+        )?;
+        mark_holder_active(property, &mut ctx.accounts.seller_holder)?;
+        mark_holder_active(property, &mut ctx.accounts.buyer_holder)?;
+
+        // Transfer fraction tokens using the seller-approved delegate PDA
+        let property_key = property.key();
+        let payment_decimals = property.payment_decimals;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"order_delegate", property_key.as_ref(), &[ctx.bumps.order_delegate]]];
+        let cpi_accounts_f = Transfer {
+            from: ctx.accounts.seller_fraction_ata.to_account_info(),
+            to: ctx.accounts.buyer_fraction_ata.to_account_info(),
+            authority: ctx.accounts.order_delegate.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts_f, signer_seeds),
+            order.fraction_amount,
+        )?;
+
+        // Mark the nonce as used
+        let order_nonce = &mut ctx.accounts.order_nonce;
+        order_nonce.seller = order.seller;
+        order_nonce.nonce = order.nonce;
+        order_nonce.filled_at = now;
+        order_nonce.bump = ctx.bumps.order_nonce;
+
+        ctx.accounts.price_oracle.record_fill(property_key, now, order.price_per_fraction, order.fraction_amount, price, payment_decimals)?;
+
+        emit!(FractionsTraded {
+            property: property_key,
+            buyer: ctx.accounts.buyer.key(),
+            seller: order.seller,
+            fraction_amount: order.fraction_amount,
+            price_per_fraction: order.price_per_fraction,
+            price,
+            protocol_fee: fees.protocol_fee,
+            sponsor_fee: fees.sponsor_fee,
+        });
+
+        Ok(())
+    }
+
+    /// List fractions for sale on-chain. The fractions move into an escrow token
+    /// account owned by the listing PDA and keep earning rent for the seller.
+    /// With ROFR enabled, the listing opens to existing holders only until `rofr_ends_at`.
+    pub fn create_listing(
+        ctx: Context<CreateListing>,
+        fraction_amount: u64,
+        price_per_fraction: u64,
+        valid_until: i64,
+    ) -> Result<()> {
         let property = &ctx.accounts.property_account;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(fraction_amount > 0, ErrorCode::InvalidAmount);
+        require!(now < valid_until, ErrorCode::OrderExpired);
+        let seller_holder = &mut ctx.accounts.seller_holder;
+        require!(seller_holder.balance >= fraction_amount, ErrorCode::InsufficientFunds);
+        seller_holder.listed = fraction_amount;
+        mark_holder_active(property, seller_holder)?;
+
+        let listing = &mut ctx.accounts.listing;
+        listing.property = property.key();
+        listing.seller = ctx.accounts.seller.key();
+        listing.fraction_amount = fraction_amount;
+        listing.price_per_fraction = price_per_fraction;
+        listing.created_at = now;
+        listing.rofr_ends_at = now.checked_add(property.rofr_window_secs).ok_or(ErrorCode::NumericOverflow)?;
+        listing.valid_until = valid_until;
+        listing.bump = ctx.bumps.listing;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.seller_fraction_ata.to_account_info(),
+            to: ctx.accounts.listing_escrow.to_account_info(),
+            authority: ctx.accounts.seller.to_account_info(),
+        };
+        token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), fraction_amount)?;
+
+        Ok(())
+    }
+
+    /// Buy from an on-chain listing out of its escrow. While the ROFR window is open
+    /// only buyers with a non-zero holder balance may fill. The listing and its
+    /// escrow are closed once empty.
+    pub fn fill_listing(ctx: Context<FillListing>, fraction_amount: u64, max_total_cost: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        accrue_rent(&mut ctx.accounts.property_account, now)?;
+        let property = &mut ctx.accounts.property_account;
+        let listing = &ctx.accounts.listing;
+
+        require!(fraction_amount > 0 && fraction_amount <= listing.fraction_amount, ErrorCode::InvalidAmount);
+        require!(now <= listing.valid_until, ErrorCode::OrderExpired);
+        require_keys_neq!(ctx.accounts.buyer.key(), listing.seller, ErrorCode::SelfTrade);
+        if now < listing.rofr_ends_at {
+            require!(ctx.accounts.buyer_holder.balance > 0, ErrorCode::RofrWindowActive);
+        }
+
+        let price = trade_cost(fraction_amount, listing.price_per_fraction, property.fraction_decimal)?;
+        require!(price <= max_total_cost, ErrorCode::SlippageExceeded);
+
+        let fees = trade_fees(
+            &ctx.accounts.protocol_config,
+            property,
+            &ctx.accounts.buyer.key(),
+            &listing.seller,
+            price,
+        )?;
+        pay_trade_usdc(
+            &ctx.accounts.token_program,
+            ctx.accounts.buyer.as_ref(),
+            &ctx.accounts.buyer_usdc,
+            &ctx.accounts.seller_usdc,
+            &ctx.accounts.treasury_usdc,
+            &ctx.accounts.sponsor_usdc,
+            &fees,
+        )?;
+
+        init_holder(&mut ctx.accounts.buyer_holder, ctx.accounts.buyer.key(), property, ctx.bumps.buyer_holder)?;
+        move_holder_balance(
+            property,
+            &mut ctx.accounts.seller_holder,
+            &mut ctx.accounts.buyer_holder,
+            fraction_amount,
+        )?;
+        mark_holder_active(property, &mut ctx.accounts.buyer_holder)?;
+
+        let seller_holder = &mut ctx.accounts.seller_holder;
+        seller_holder.listed = seller_holder.listed.checked_sub(fraction_amount).ok_or(ErrorCode::NumericOverflow)?;
+
+        let property_key = property.key();
+        let payment_decimals = property.payment_decimals;
+        let seller_key = listing.seller;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"listing", property_key.as_ref(), seller_key.as_ref(), &[listing.bump]]];
+        let cpi_accounts_f = Transfer {
+            from: ctx.accounts.listing_escrow.to_account_info(),
+            to: ctx.accounts.buyer_fraction_ata.to_account_info(),
+            authority: listing.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts_f, signer_seeds),
+            fraction_amount,
+        )?;
+
+        ctx.accounts.price_oracle.record_fill(property_key, now, listing.price_per_fraction, fraction_amount, price, payment_decimals)?;
+
+        emit!(FractionsTraded {
+            property: property_key,
+            buyer: ctx.accounts.buyer.key(),
+            seller: listing.seller,
+            fraction_amount,
+            price_per_fraction: listing.price_per_fraction,
+            price,
+            protocol_fee: fees.protocol_fee,
+            sponsor_fee: fees.sponsor_fee,
+        });
+
+        let listing = &mut ctx.accounts.listing;
+        listing.fraction_amount = listing.fraction_amount.checked_sub(fraction_amount).ok_or(ErrorCode::InsufficientFunds)?;
+        if listing.fraction_amount == 0 {
+            let cpi_accounts = CloseAccount {
+                account: ctx.accounts.listing_escrow.to_account_info(),
+                destination: ctx.accounts.seller.to_account_info(),
+                authority: listing.to_account_info(),
+            };
+            token::close_account(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds))?;
+            listing.close(ctx.accounts.seller.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    /// Cancel a listing: return the escrowed fractions to the seller and close
+    /// the escrow and listing accounts.
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        let listing = &ctx.accounts.listing;
+        let property_key = ctx.accounts.property_account.key();
+        let seller_key = ctx.accounts.seller.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"listing", property_key.as_ref(), seller_key.as_ref(), &[listing.bump]]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.listing_escrow.to_account_info(),
+            to: ctx.accounts.seller_fraction_ata.to_account_info(),
+            authority: listing.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds),
+            ctx.accounts.listing_escrow.amount,
+        )?;
+
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.listing_escrow.to_account_info(),
+            destination: ctx.accounts.seller.to_account_info(),
+            authority: listing.to_account_info(),
+        };
+        token::close_account(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds))?;
+
+        let seller_holder = &mut ctx.accounts.seller_holder;
+        seller_holder.listed = 0;
+        mark_holder_active(&ctx.accounts.property_account, seller_holder)
+    }
+
+    /// Return the time-weighted average trade price per whole fraction over the
+    /// last `window_secs`. Fails if the oracle has no observation that old.
+    pub fn get_twap(ctx: Context<GetTwap>, window_secs: i64) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.price_oracle.twap(now, window_secs)
+    }
+
+    /// Return the holder's claimable rent in USDC (before withholding) as of now.
+    /// Read-only; simulate to read the return data.
+    pub fn get_pending_rent(ctx: Context<HolderView>) -> Result<u64> {
+        let (_, holder) = settled_view(&ctx.accounts.property_account, &ctx.accounts.holder_state)?;
+        Ok((holder.unclaimed / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?)
+    }
+
+    /// Return the holder's balance and everything claimable as of now.
+    /// Read-only; simulate to read the return data.
+    pub fn get_holder_position(ctx: Context<HolderView>) -> Result<HolderPosition> {
+        let (property, holder) = settled_view(&ctx.accounts.property_account, &ctx.accounts.holder_state)?;
 
-        // Settle pending rewards for source
-        let src = &mut ctx.accounts.source_holder;
-        let dest = &mut ctx.accounts.dest_holder;
+        let mut pending_rewards = [0u64; MAX_REWARD_MINTS];
+        for (pending, unclaimed) in pending_rewards.iter_mut().zip(holder.reward_unclaimed).take(property.reward_count as usize) {
+            *pending = (unclaimed / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?;
+        }
 
-        let src_balance = src.balance as u128;
-        let dest_balance = dest.balance as u128;
+        Ok(HolderPosition {
+            balance: holder.balance,
+            minted_fractions: property.minted_fractions,
+            pending_rent: (holder.unclaimed / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?,
+            pending_rewards,
+            pending_scrip: (holder.scrip_unclaimed / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?,
+            tax_withheld: holder.tax_withheld,
+            last_settled_epoch: holder.last_settled_epoch,
+        })
+    }
 
-        let accrued_src = src_balance.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
-        let pending_src = accrued_src.checked_sub(src.reward_debt).unwrap_or(0u128);
-        src.unclaimed = src.unclaimed.checked_add(pending_src).ok_or(ErrorCode::NumericOverflow)?;
+    /// Quote the USDC leg of a trade, including protocol and sponsor fees.
+    /// Read-only; simulate to read the return data.
+    pub fn quote_trade(
+        ctx: Context<QuoteTrade>,
+        fraction_amount: u64,
+        price_per_fraction: u64,
+        buyer: Pubkey,
+        seller: Pubkey,
+    ) -> Result<TradeQuote> {
+        let property = &ctx.accounts.property_account;
+        let price = trade_cost(fraction_amount, price_per_fraction, property.fraction_decimal)?;
+        let fees = trade_fees(&ctx.accounts.protocol_config, property, &buyer, &seller, price)?;
 
-        let accrued_dest = dest_balance.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
-        let pending_dest = accrued_dest.checked_sub(dest.reward_debt).unwrap_or(0u128);
-        dest.unclaimed = dest.unclaimed.checked_add(pending_dest).ok_or(ErrorCode::NumericOverflow)?;
+        Ok(TradeQuote {
+            price,
+            protocol_fee: fees.protocol_fee,
+            sponsor_fee: fees.sponsor_fee,
+            seller_proceeds: fees.seller_proceeds,
+        })
+    }
 
-        // Update balances
-        src.balance = src.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
-        dest.balance = dest.balance.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+    /// Transfer fractions between two holders. All transfers must go through
+    /// the program to correctly update reward accounting. With ROFR enabled,
+    /// only existing holders may receive fractions this way.
+    pub fn transfer_fractions(ctx: Context<TransferFractions>, amount: u64) -> Result<()> {
+        accrue_rent(&mut ctx.accounts.property_account, Clock::get()?.unix_timestamp)?;
+        let property = &mut ctx.accounts.property_account;
+        require_rofr_direct_sale(property, &ctx.accounts.dest_holder)?;
 
-        // Update reward_debt to new_balance * cum
-        let new_src_balance_u128 = src.balance as u128;
-        let new_dest_balance_u128 = dest.balance as u128;
-        src.reward_debt = new_src_balance_u128.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
-        dest.reward_debt = new_dest_balance_u128.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
+        // Settle pending rewards for both holders and move the tracked balance
+        init_holder(&mut ctx.accounts.dest_holder, ctx.accounts.dest_owner.key(), property, ctx.bumps.dest_holder)?;
+        move_holder_balance(
+            property,
+            &mut ctx.accounts.source_holder,
+            &mut ctx.accounts.dest_holder,
+            amount,
+        )?;
+        mark_holder_active(property, &mut ctx.accounts.source_holder)?;
+        mark_holder_active(property, &mut ctx.accounts.dest_holder)?;
 
         // Perform token transfer: require source owner signature, then CPI transfer
         let cpi_accounts = Transfer {
@@ -197,330 +646,3443 @@ pub mod rwa {
     }
 
     /// Deposit rent (USDC) into RentVault PDA and update cumulative rent per share.
-    pub fn deposit_rent(ctx: Context<DepositRent>, amount: u64) -> Result<()> {
+    /// The inflow is split in order: management fee to the sponsor, unfunded
+    /// expenses, `reserve_bps` to the reserve vault, then a performance fee on
+    /// distributions above the hurdle; holders are credited the net.
+    /// In streaming mode the deposit is released into `cum_rent_per_share`
+    /// linearly over `stream_period_secs` instead.
+    /// Each deposit opens a `DistributionEpoch`. A future `record_date` defers the
+    /// credit until then (ex-dividend); supply changes and further deposits wait.
+    /// Deposits are divided over `minted_fractions` and wait while the supply is halted.
+    /// Only the authority, the manager and registered rent payers may deposit, and
+    /// only the first two may set a future record date; `reference` (lease id,
+    /// period, ...) is stored on the epoch.
+    pub fn deposit_rent(ctx: Context<DepositRent>, amount: u64, record_date: i64, reference: [u8; 32]) -> Result<()> {
         let property = &mut ctx.accounts.property_account;
-        // Transfer USDC from payer to rent vault
+        let payer_key = ctx.accounts.payer.key();
+        let operator = payer_key == property.authority || payer_key == property.manager;
+        require!(operator || ctx.accounts.rent_payer.is_some(), ErrorCode::UnauthorizedPayer);
+        require!(!property.supply_halted, ErrorCode::SupplyMismatch);
+        require!(property.minted_fractions > 0, ErrorCode::NoFractions);
+        let now = Clock::get()?.unix_timestamp;
+        // A future record date defers the epoch and blocks further deposits until it passes
+        require!(operator || record_date <= now, ErrorCode::RecordDateRestricted);
+        accrue_rent(property, now)?;
+        require!(property.epoch_pending == 0, ErrorCode::EpochPending);
+
+        // Management fee is charged on gross rent
+        let management_fee = bps_of(amount, property.management_fee_bps)?;
+        let after_management = amount - management_fee;
+
+        // Fund recorded expenses before crediting holders
+        let expenses_withheld = after_management.min(property.expenses_payable);
+        property.expenses_payable -= expenses_withheld;
+        property.expense_funds = property.expense_funds.checked_add(expenses_withheld).ok_or(ErrorCode::NumericOverflow)?;
+        let after_expenses = after_management - expenses_withheld;
+
+        // Route the reserve share to the reserve vault, up to the reserve cap
+        let mut reserve_withheld = bps_of(after_expenses, property.reserve_bps)?;
+        if property.reserve_cap > 0 {
+            reserve_withheld = reserve_withheld.min(property.reserve_cap.saturating_sub(property.reserve_balance));
+        }
+        property.reserve_balance = property.reserve_balance.checked_add(reserve_withheld).ok_or(ErrorCode::NumericOverflow)?;
+        let distributable = after_expenses - reserve_withheld;
+
+        // Performance fee only on the part of trailing distributions above the hurdle
+        if property.perf_window_secs > 0 && now >= property.perf_window_start.saturating_add(property.perf_window_secs) {
+            property.perf_window_start = now;
+            property.perf_window_distributed = 0;
+        }
+        let trailing = property.perf_window_distributed.checked_add(distributable).ok_or(ErrorCode::NumericOverflow)?;
+        let above_hurdle = trailing
+            .saturating_sub(property.perf_hurdle.max(property.perf_window_distributed))
+            .min(distributable);
+        let performance_fee = bps_of(above_hurdle, property.performance_fee_bps)?;
+        property.perf_window_distributed = trailing;
+        let net_amount = distributable - performance_fee;
+
+        let sponsor_fee = management_fee.checked_add(performance_fee).ok_or(ErrorCode::NumericOverflow)?;
+
+        // Transfer USDC from payer to rent vault, sponsor and reserve vault
         let cpi_accounts = Transfer {
             from: ctx.accounts.payer_usdc.to_account_info(),
             to: ctx.accounts.rent_vault_ata.to_account_info(),
             authority: ctx.accounts.payer.to_account_info().clone(),
         };
-        token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+        token::transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            amount - reserve_withheld - sponsor_fee,
+        )?;
+        if sponsor_fee > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.payer_usdc.to_account_info(),
+                to: ctx.accounts.sponsor_usdc.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info().clone(),
+            };
+            token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), sponsor_fee)?;
+        }
+        if reserve_withheld > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.payer_usdc.to_account_info(),
+                to: ctx.accounts.reserve_vault_ata.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info().clone(),
+            };
+            token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), reserve_withheld)?;
+        }
 
-        // cum_rent_per_share += (amount * SCALE) / minted_fractions
-        let amount_u128 = amount as u128;
-        let total = property.minted_fractions as u128;
-        require!(total > 0, ErrorCode::NoFractions);
-        let increment = amount_u128.checked_mul(SCALE).ok_or(ErrorCode::NumericOverflow)?.checked_div(total).ok_or(ErrorCode::NumericOverflow)?;
-        property.cum_rent_per_share = property.cum_rent_per_share.checked_add(increment).ok_or(ErrorCode::NumericOverflow)?;
+        let scaled_amount = (net_amount as u128).checked_mul(SCALE).ok_or(ErrorCode::NumericOverflow)?;
+        require!(record_date <= now.saturating_add(MAX_RECORD_DELAY_SECS), ErrorCode::RecordDateTooFar);
+        let record_date = record_date.max(now);
+
+        // Open an epoch for this deposit
+        let epoch = &mut ctx.accounts.distribution_epoch;
+        epoch.property = property.key();
+        epoch.id = property.epoch_count;
+        epoch.record_date = record_date;
+        epoch.opened_at = now;
+        epoch.net_amount = net_amount;
+        epoch.supply = property.minted_fractions;
+        epoch.rate_per_share = scaled_amount / property.minted_fractions as u128;
+        epoch.payer = payer_key;
+        epoch.reference = reference;
+        epoch.bump = ctx.bumps.distribution_epoch;
+        property.epoch_count = property.epoch_count.checked_add(1).ok_or(ErrorCode::NumericOverflow)?;
+
+        if record_date > now {
+            // Holders of record at the record date receive it; applied lazily by accrue_rent
+            property.epoch_pending = scaled_amount;
+            property.epoch_record_date = record_date;
+        } else {
+            release_rent(property, scaled_amount, now)?;
+            property.epoch_credited = property.epoch_count;
+        }
+        property.rent_credited = property.rent_credited.checked_add(net_amount).ok_or(ErrorCode::NumericOverflow)?;
+
+        emit!(RentDeposited {
+            property: property.key(),
+            payer: payer_key,
+            reference,
+            gross_amount: amount,
+            management_fee,
+            performance_fee,
+            expenses_withheld,
+            reserve_withheld,
+            net_amount,
+        });
 
         Ok(())
     }
 
+    /// Move the pending epoch's record date forward to now and credit it to the
+    /// current holders, e.g. to unblock mints and deposits after a record date set
+    /// by mistake. Only property authority may call.
+    pub fn apply_pending_epoch(ctx: Context<ApplyPendingEpoch>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        require!(property.epoch_pending > 0, ErrorCode::NoPendingEpoch);
+        let now = Clock::get()?.unix_timestamp;
+
+        property.epoch_record_date = now;
+        ctx.accounts.distribution_epoch.record_date = now;
+        accrue_rent(property, now)
+    }
+
     /// Claim rent for a holder: pay out unclaimed + newly accrued amount.
+    /// Withholding from the holder's tax profile goes to the property tax vault;
+    /// `tax_vault_ata` is only needed when tax is withheld. The payout is recorded
+    /// in the holder's TaxYearRecord for the current UTC year.
+    /// The holder or their claim delegate may call; payment goes to the holder's
+    /// payout destination, or a token account they own if none is set. DRIP holders
+    /// are paid through `reinvest_rent` instead.
     pub fn claim_rent(ctx: Context<ClaimRent>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let withholding_bps = profile_withholding_bps(&ctx.accounts.tax_profile, ctx.program_id)?;
+        let holder_key = ctx.accounts.holder_wallet.key();
+        let payer_key = ctx.accounts.payer.key();
+        require!(
+            payer_key == holder_key || payer_key == ctx.accounts.holder_state.claim_delegate,
+            ErrorCode::Unauthorized
+        );
+        require!(!ctx.accounts.holder_state.drip_enabled, ErrorCode::DripEnabled);
+        require_payout_destination(&ctx.accounts.holder_state, &ctx.accounts.receiver_usdc)?;
+
         let holder = &mut ctx.accounts.holder_state;
-        let property = &ctx.accounts.property_account;
+        let property = &mut ctx.accounts.property_account;
+        accrue_rent(property, now)?;
 
-        // If holder_state was just initialized, set initial values
-        if holder.balance == 0 && holder.reward_debt == 0 {
-            // Get actual balance from the holder's fraction token account
-            let fraction_balance = ctx.accounts.holder_fraction_ata.amount;
-            holder.holder = ctx.accounts.payer.key();
-            holder.property = property.key();
-            holder.balance = fraction_balance;
-            holder.reward_debt = (fraction_balance as u128).checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
-            holder.unclaimed = 0;
-            holder.bump = ctx.bumps.holder_state;
+        settle_holder(property, holder)?;
+        reset_holder_debt(property, holder)?;
+        if payer_key == holder_key {
+            mark_holder_active(property, holder)?;
         }
+        pay_rent(
+            ctx.program_id,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            property,
+            holder,
+            &ctx.accounts.rent_vault,
+            &ctx.accounts.rent_vault_ata,
+            &ctx.accounts.receiver_usdc,
+            &ctx.accounts.tax_vault_ata,
+            &ctx.accounts.tax_year_record,
+            &ctx.accounts.payer,
+            withholding_bps,
+        )?;
 
-        let balance_u128 = holder.balance as u128;
-        let accrued = balance_u128.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
-        let pending = accrued.checked_sub(holder.reward_debt).unwrap_or(0u128);
+        Ok(())
+    }
 
-        // total pending = holder.unclaimed + pending
-        let total_pending = holder.unclaimed.checked_add(pending).ok_or(ErrorCode::NumericOverflow)?;
+    /// Distribute `fraction_amount` new fractions pro-rata to current holders instead
+    /// of cash. The fractions are reserved against `total_fractions` and minted as
+    /// holders call `claim_scrip`. Only property authority may call.
+    pub fn declare_scrip_dividend(ctx: Context<DeclareScripDividend>, fraction_amount: u64) -> Result<()> {
+        require!(fraction_amount > 0, ErrorCode::InvalidAmount);
+        let property = &mut ctx.accounts.property_account;
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
+        require!(!property.supply_halted, ErrorCode::SupplyMismatch);
+        let total = property.minted_fractions as u128;
+        require!(total > 0, ErrorCode::NoFractions);
 
-        let payout_u128 = total_pending.checked_div(SCALE).ok_or(ErrorCode::NumericOverflow)?;
-        let payout: u64 = payout_u128.try_into().map_err(|_| ErrorCode::NumericOverflow)?;
+        let reserved = property.scrip_reserved.checked_add(fraction_amount).ok_or(ErrorCode::NumericOverflow)?;
+        require!(
+            property.minted_fractions.checked_add(reserved).ok_or(ErrorCode::NumericOverflow)? <= property.total_fractions,
+            ErrorCode::ExceedsTotalFractions
+        );
+        property.scrip_reserved = reserved;
 
-        if payout > 0 {
-            // Transfer USDC from rent vault to user
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.rent_vault_ata.to_account_info(),
-                to: ctx.accounts.receiver_usdc.to_account_info(),
-                authority: ctx.accounts.rent_vault.to_account_info().clone(),
-            };
-            // rent_vault is a PDA; sign with its seeds
-            let (_pda, bump) = Pubkey::find_program_address(&[b"rent_vault", ctx.accounts.property_account.fraction_mint.as_ref()], ctx.program_id);
-            let signer_seeds: &[&[&[u8]]] = &[&[b"rent_vault", ctx.accounts.property_account.fraction_mint.as_ref(), &[bump]]];
-            token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), payout)?;
-        }
+        let numerator = (fraction_amount as u128)
+            .checked_mul(SCALE).ok_or(ErrorCode::NumericOverflow)?
+            .checked_add(property.scrip_remainder).ok_or(ErrorCode::NumericOverflow)?;
+        property.cum_scrip_per_share = property.cum_scrip_per_share
+            .checked_add(numerator / total).ok_or(ErrorCode::NumericOverflow)?;
+        property.scrip_remainder = numerator % total;
 
-        // Reset unclaimed + update reward_debt
-        holder.unclaimed = 0u128;
-        holder.reward_debt = balance_u128.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
+        emit!(ScripDeclared { property: property.key(), fraction_amount });
 
         Ok(())
     }
 
-    /// Burn fractions: holder burns their fraction tokens, reducing total supply.
-    /// This is typically used when closing or liquidating a property.
-    pub fn burn_fractions(ctx: Context<BurnFractions>, amount: u64) -> Result<()> {
+    /// Mint the caller's accrued scrip dividend fractions.
+    pub fn claim_scrip(ctx: Context<ClaimScrip>) -> Result<()> {
         let property = &mut ctx.accounts.property_account;
-        let holder = &mut ctx.accounts.holder_state;
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
+        require!(property.epoch_pending == 0, ErrorCode::EpochPending);
 
-        // Settle pending rewards before burning
-        let balance_u128 = holder.balance as u128;
-        let accrued = balance_u128.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
-        let pending = accrued.checked_sub(holder.reward_debt).unwrap_or(0u128);
-        holder.unclaimed = holder.unclaimed.checked_add(pending).ok_or(ErrorCode::NumericOverflow)?;
+        let holder = &mut ctx.accounts.holder_state;
+        settle_holder(property, holder)?;
+        let amount: u64 = (holder.scrip_unclaimed / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?;
+        require!(amount > 0, ErrorCode::NoPendingRewards);
+        holder.scrip_unclaimed %= SCALE;
 
-        // Update holder balance
-        holder.balance = holder.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
-        let new_balance_u128 = holder.balance as u128;
-        holder.reward_debt = new_balance_u128.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
+        add_holder_balance(holder, amount, Clock::get()?.unix_timestamp)?;
+        reset_holder_debt(property, holder)?;
+        mark_holder_active(property, holder)?;
+        property.minted_fractions = property.minted_fractions.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+        property.scrip_reserved = property.scrip_reserved.checked_sub(amount).ok_or(ErrorCode::NumericOverflow)?;
+        property.tracked_balance = property.tracked_balance.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
 
-        // Burn tokens via CPI
-        let cpi_accounts = token::Burn {
+        let property_key = property.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"fraction_authority", property_key.as_ref(), &[ctx.bumps.mint_authority]]];
+        let cpi_accounts = MintTo {
             mint: ctx.accounts.fraction_mint.to_account_info(),
-            from: ctx.accounts.holder_fraction_ata.to_account_info(),
-            authority: ctx.accounts.holder.to_account_info().clone(),
+            to: ctx.accounts.holder_fraction_ata.to_account_info(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
         };
-        token::burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+        token::mint_to(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), amount)?;
+
+        emit!(ScripClaimed { property: property_key, holder: ctx.accounts.holder.key(), fraction_amount: amount });
 
         Ok(())
     }
 
-    /// Unlock NFT from escrow vault: only allowed when all fractions are burned.
-    /// Transfers the NFT back to the property authority.
-    pub fn unlock_nft(ctx: Context<UnlockNft>) -> Result<()> {
-        let property = &ctx.accounts.property_account;
-        
-        // Verify property authority is calling
-        require!(ctx.accounts.authority.key() == property.authority, ErrorCode::Unauthorized);
+    /// Set the token account rent claims must pay to. `Pubkey::default()` clears it,
+    /// requiring a token account owned by the holder.
+    pub fn set_payout_destination(ctx: Context<UpdateHolderSettings>, payout_destination: Pubkey) -> Result<()> {
+        ctx.accounts.holder_state.payout_destination = payout_destination;
+        mark_holder_active(&ctx.accounts.property_account, &mut ctx.accounts.holder_state)
+    }
 
-        // Verify all fractions are burned (supply = 0)
-        let fraction_supply = ctx.accounts.fraction_mint.supply;
-        require!(fraction_supply == 0, ErrorCode::FractionsNotBurned);
+    /// Allow `claim_delegate` to trigger `claim_rent` on the holder's behalf. The
+    /// delegate cannot change where the rent is paid. `Pubkey::default()` revokes.
+    pub fn set_claim_delegate(ctx: Context<UpdateHolderSettings>, claim_delegate: Pubkey) -> Result<()> {
+        ctx.accounts.holder_state.claim_delegate = claim_delegate;
+        mark_holder_active(&ctx.accounts.property_account, &mut ctx.accounts.holder_state)
+    }
 
-        // Transfer NFT from vault to authority
-        let nft_mint_key = ctx.accounts.nft_mint.key();
+    /// Opt in or out of dividend reinvestment (DRIP) for this property, up to
+    /// `max_price` per fraction. While enabled, rent is not paid out in cash.
+    pub fn set_drip(ctx: Context<UpdateHolderSettings>, enabled: bool, max_price: u64) -> Result<()> {
+        require!(!enabled || max_price > 0, ErrorCode::InvalidAmount);
+        ctx.accounts.holder_state.drip_enabled = enabled;
+        ctx.accounts.holder_state.drip_max_price = max_price;
+        mark_holder_active(&ctx.accounts.property_account, &mut ctx.accounts.holder_state)
+    }
+
+    /// Reinvest a DRIP holder's pending rent into newly minted fractions at the
+    /// offering price, if it is within the holder's `drip_max_price`; the USDC goes
+    /// to the sponsor and is recorded in the holder's TaxYearRecord. Sub-fraction
+    /// change stays pending. Anyone may call and pays for a new tax year record.
+    pub fn reinvest_rent(ctx: Context<ReinvestRent>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
+        require!(ctx.accounts.holder_state.drip_enabled, ErrorCode::DripNotEnabled);
+        require!(property.offering_price > 0, ErrorCode::NoOffering);
+        require!(property.offering_price <= ctx.accounts.holder_state.drip_max_price, ErrorCode::DripPriceTooHigh);
+        require!(!property.supply_halted, ErrorCode::SupplyMismatch);
+        require!(property.epoch_pending == 0, ErrorCode::EpochPending);
+        require!(
+            profile_withholding_bps(&ctx.accounts.tax_profile, ctx.program_id)? == 0,
+            ErrorCode::WithholdingRequiresClaimRent
+        );
+
+        let holder = &mut ctx.accounts.holder_state;
+        settle_holder(property, holder)?;
+        reset_holder_debt(property, holder)?;
+
+        // Whole fraction units the pending rent buys, capped by the unissued supply
+        let pending: u64 = (holder.unclaimed / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?;
+        let unit = 10u128.checked_pow(property.fraction_decimal as u32).ok_or(ErrorCode::NumericOverflow)?;
+        let affordable: u64 = ((pending as u128).checked_mul(unit).ok_or(ErrorCode::NumericOverflow)? / property.offering_price as u128)
+            .try_into().map_err(|_| ErrorCode::NumericOverflow)?;
+        let unissued = property.total_fractions
+            .saturating_sub(property.minted_fractions)
+            .saturating_sub(property.scrip_reserved);
+        let fractions = affordable.min(unissued);
+        require!(fractions > 0, ErrorCode::InvalidAmount);
+        let cost = trade_cost(fractions, property.offering_price, property.fraction_decimal)?;
+
+        holder.unclaimed = holder.unclaimed
+            .checked_sub((cost as u128).checked_mul(SCALE).ok_or(ErrorCode::NumericOverflow)?)
+            .ok_or(ErrorCode::NumericOverflow)?;
+        add_holder_balance(holder, fractions, Clock::get()?.unix_timestamp)?;
+        reset_holder_debt(property, holder)?;
+        property.minted_fractions = property.minted_fractions.checked_add(fractions).ok_or(ErrorCode::NumericOverflow)?;
+        property.tracked_balance = property.tracked_balance.checked_add(fractions).ok_or(ErrorCode::NumericOverflow)?;
+        property.rent_paid_out = property.rent_paid_out.checked_add(cost).ok_or(ErrorCode::NumericOverflow)?;
+
+        let property_key = property.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"fraction_authority", property_key.as_ref(), &[ctx.bumps.mint_authority]]];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.fraction_mint.to_account_info(),
+            to: ctx.accounts.holder_fraction_ata.to_account_info(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
+        };
+        token::mint_to(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), fractions)?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"rent_vault", property.fraction_mint.as_ref(), &[ctx.bumps.rent_vault]]];
         let cpi_accounts = Transfer {
-            from: ctx.accounts.nft_vault_ata.to_account_info(),
-            to: ctx.accounts.authority_nft_ata.to_account_info(),
-            authority: ctx.accounts.nft_vault.to_account_info().clone(),
+            from: ctx.accounts.rent_vault_ata.to_account_info(),
+            to: ctx.accounts.sponsor_usdc.to_account_info(),
+            authority: ctx.accounts.rent_vault.to_account_info(),
         };
-        
-        // Sign with nft_vault PDA seeds
-        let (_pda, bump) = Pubkey::find_program_address(&[b"nft_vault", nft_mint_key.as_ref()], ctx.program_id);
-        let signer_seeds: &[&[&[u8]]] = &[&[b"nft_vault", nft_mint_key.as_ref(), &[bump]]];
-        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), 1)?;
+        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), cost)?;
+
+        record_tax_year(
+            &ctx.accounts.tax_year_record,
+            &ctx.accounts.caller,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            property_key,
+            ctx.accounts.holder_wallet.key(),
+            cost,
+            0,
+        )?;
+
+        emit!(RentReinvested {
+            property: property_key,
+            holder: ctx.accounts.holder_wallet.key(),
+            fraction_amount: fractions,
+            price_per_fraction: property.offering_price,
+            cost,
+        });
 
         Ok(())
     }
-}
-
-// --------------------
-// Accounts and Contexts
-// --------------------
 
-#[account]
-pub struct PropertyAccount {
-    pub property_key: Pubkey,
-    pub authority: Pubkey,
-    pub nft_mint: Pubkey,
-    pub fraction_mint: Pubkey,
-    pub total_fractions: u64,
-    pub minted_fractions: u64,  // Track actual circulating supply
-    pub fraction_decimal: u8,
-    pub cum_rent_per_share: u128,
-    pub bump: u8,
-    pub metadata_uri: [u8; 200],  // Fixed size for IPFS/Arweave URIs
-}
+    /// Push pending rent to a batch of holders. Anyone may call. `remaining_accounts`
+    /// holds [holder_state, payout USDC account, tax_profile, tax_year_record] tuples;
+    /// the USDC account must be the holder's payout destination. Tax is withheld and
+    /// recorded as in `claim_rent`, so `tax_vault_ata` is required once a holder in the
+    /// batch is subject to withholding, and the caller pays for new tax year records.
+    /// The caller earns `crank_reward_bps` of each payout out of `crank_funds`; holders
+    /// are always paid in full. DRIP holders are skipped.
+    pub fn distribute_rent_batch<'info>(ctx: Context<'_, '_, 'info, 'info, DistributeRentBatch<'info>>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        let now = Clock::get()?.unix_timestamp;
+        accrue_rent(property, now)?;
+        let tuples = ctx.remaining_accounts.chunks_exact(4);
+        require!(tuples.remainder().is_empty(), ErrorCode::InvalidRemainingAccounts);
 
-#[account]
-pub struct HolderState {
-    pub holder: Pubkey,
-    pub property: Pubkey,
-    pub balance: u64,
-    pub reward_debt: u128,
-    pub unclaimed: u128,
-    pub bump: u8,
-}
+        let property_key = property.key();
+        let vault_mint = ctx.accounts.rent_vault_ata.mint;
+        let fraction_mint = property.fraction_mint;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"rent_vault", fraction_mint.as_ref(), &[ctx.bumps.rent_vault]]];
+        let mut total_crank_reward: u64 = 0;
 
-#[derive(Accounts)]
-pub struct InitializeProperty<'info> {
-    #[account(init, payer = authority, space = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 1 + 16 + 1 + 4 + 200)]
-    pub property_account: Account<'info, PropertyAccount>,
+        for tuple in tuples {
+            let (holder_info, receiver_info, tax_profile_info, record_info) = (&tuple[0], &tuple[1], &tuple[2], &tuple[3]);
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+            let mut holder = Account::<HolderState>::try_from(holder_info)?;
+            require!(holder.initialized, ErrorCode::InvalidHolderState);
+            let (expected_holder, _) = Pubkey::find_program_address(
+                &[b"holder", holder.holder.as_ref(), property_key.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(holder_info.key(), expected_holder, ErrorCode::InvalidHolderState);
+            // DRIP holders are paid through reinvest_rent
+            if holder.drip_enabled {
+                continue;
+            }
 
-    /// CHECK: NFT mint pubkey reference
-    pub nft_mint: AccountInfo<'info>,
+            let receiver = Account::<TokenAccount>::try_from(receiver_info)?;
+            require!(receiver.mint == vault_mint, ErrorCode::MintMismatch);
+            require_payout_destination(&holder, &receiver)?;
 
-    /// CHECK: Fraction mint (SPL) account to be created via CPI - must be signer for system create_account
-    #[account(mut, signer)]
-    pub fraction_mint: AccountInfo<'info>,
+            let (expected_profile, _) = Pubkey::find_program_address(&[b"tax_profile", holder.holder.as_ref()], ctx.program_id);
+            require_keys_eq!(tax_profile_info.key(), expected_profile, ErrorCode::InvalidTaxProfile);
+            let withholding_bps = profile_withholding_bps(tax_profile_info, ctx.program_id)?;
 
-    /// CHECK: Mint authority PDA for the fraction mint
-    /// seeds: ["fraction_authority", property_account]
-    pub mint_authority: UncheckedAccount<'info>,
+            settle_holder(property, &mut holder)?;
+            reset_holder_debt(property, &mut holder)?;
 
-    /// CHECK: Rent vault PDA
-    pub rent_vault: UncheckedAccount<'info>,
-    /// CHECK: NFT vault PDA
-    pub nft_vault: UncheckedAccount<'info>,
+            let payout: u64 = (holder.unclaimed / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?;
+            if payout == 0 {
+                holder.exit(ctx.program_id)?;
+                continue;
+            }
+            holder.unclaimed %= SCALE;
 
-    /// Associated token accounts (should be created client-side)
+            let withheld = bps_of(payout, withholding_bps)?;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.rent_vault_ata.to_account_info(),
+                to: receiver_info.clone(),
+                authority: ctx.accounts.rent_vault.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds),
+                payout - withheld,
+            )?;
+
+            if withheld > 0 {
+                let tax_vault_ata = require_tax_vault(&ctx.accounts.tax_vault_ata, property, ctx.program_id)?;
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.rent_vault_ata.to_account_info(),
+                    to: tax_vault_ata.to_account_info(),
+                    authority: ctx.accounts.rent_vault.to_account_info(),
+                };
+                token::transfer(
+                    CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds),
+                    withheld,
+                )?;
+                holder.tax_withheld = holder.tax_withheld.checked_add(withheld).ok_or(ErrorCode::NumericOverflow)?;
+
+                emit!(RentWithheld {
+                    property: property_key,
+                    holder: holder.holder,
+                    tax_year: unix_year(now) as u16,
+                    gross_amount: payout,
+                    withheld,
+                });
+            }
+            holder.exit(ctx.program_id)?;
+            record_tax_year(
+                record_info,
+                &ctx.accounts.cranker,
+                &ctx.accounts.system_program,
+                ctx.program_id,
+                property_key,
+                holder.holder,
+                payout,
+                withheld,
+            )?;
+
+            let crank_reward = bps_of(payout, property.crank_reward_bps)?
+                .min(property.crank_funds - total_crank_reward);
+            total_crank_reward += crank_reward;
+            property.rent_paid_out = property.rent_paid_out.checked_add(payout).ok_or(ErrorCode::NumericOverflow)?;
+        }
+
+        if total_crank_reward > 0 {
+            property.crank_funds -= total_crank_reward;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.rent_vault_ata.to_account_info(),
+                to: ctx.accounts.cranker_usdc.to_account_info(),
+                authority: ctx.accounts.rent_vault.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds),
+                total_crank_reward,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Claim rent from several properties into one USDC account. `remaining_accounts`
+    /// holds [property, holder_state, rent_vault_ata, rent_vault, tax_year_record] tuples.
+    /// The USDC account must satisfy each holder state's payout destination. Not available
+    /// to holders subject to withholding or DRIP holders.
+    pub fn claim_rent_multi<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimRentMulti<'info>>) -> Result<()> {
+        let tuples = ctx.remaining_accounts.chunks_exact(5);
+        require!(
+            !ctx.remaining_accounts.is_empty() && tuples.remainder().is_empty(),
+            ErrorCode::InvalidRemainingAccounts
+        );
+        require!(
+            profile_withholding_bps(&ctx.accounts.tax_profile, ctx.program_id)? == 0,
+            ErrorCode::WithholdingRequiresClaimRent
+        );
+        let holder_key = ctx.accounts.holder.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        for tuple in tuples {
+            let (property_info, holder_info, vault_ata_info, vault_info, record_info) =
+                (&tuple[0], &tuple[1], &tuple[2], &tuple[3], &tuple[4]);
+
+            let mut property = Account::<PropertyAccount>::try_from(property_info)?;
+            let (expected_holder, _) = Pubkey::find_program_address(
+                &[b"holder", holder_key.as_ref(), property_info.key.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(holder_info.key(), expected_holder, ErrorCode::InvalidHolderState);
+            let mut holder = Account::<HolderState>::try_from(holder_info)?;
+            require!(!holder.drip_enabled, ErrorCode::DripEnabled);
+            require_payout_destination(&holder, &ctx.accounts.receiver_usdc)?;
+
+            let (expected_vault, vault_bump) = Pubkey::find_program_address(
+                &[b"rent_vault", property.fraction_mint.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(vault_info.key(), expected_vault, ErrorCode::InvalidRentVault);
+            let vault_ata = Account::<TokenAccount>::try_from(vault_ata_info)?;
+            require_keys_eq!(vault_ata.owner, expected_vault, ErrorCode::InvalidRentVault);
+            require_keys_eq!(vault_ata.mint, ctx.accounts.receiver_usdc.mint, ErrorCode::MintMismatch);
+
+            accrue_rent(&mut property, now)?;
+            settle_holder(&mut property, &mut holder)?;
+            reset_holder_debt(&property, &mut holder)?;
+            mark_holder_active(&property, &mut holder)?;
+
+            let payout: u64 = (holder.unclaimed / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?;
+            if payout > 0 {
+                let cpi_accounts = Transfer {
+                    from: vault_ata_info.clone(),
+                    to: ctx.accounts.receiver_usdc.to_account_info(),
+                    authority: vault_info.clone(),
+                };
+                let signer_seeds: &[&[&[u8]]] = &[&[b"rent_vault", property.fraction_mint.as_ref(), &[vault_bump]]];
+                token::transfer(
+                    CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds),
+                    payout,
+                )?;
+                property.rent_paid_out = property.rent_paid_out.checked_add(payout).ok_or(ErrorCode::NumericOverflow)?;
+            }
+            holder.unclaimed %= SCALE;
+            record_tax_year(
+                record_info,
+                &ctx.accounts.holder,
+                &ctx.accounts.system_program,
+                ctx.program_id,
+                property_info.key(),
+                holder_key,
+                payout,
+                0,
+            )?;
+
+            property.exit(ctx.program_id)?;
+            holder.exit(ctx.program_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sweep rent vault tokens that no holder is entitled to (e.g. tokens sent to
+    /// the vault outside of `deposit_rent`). Everything credited to holders and not
+    /// yet paid out, funds withheld for expenses and the crank budget stay in the vault.
+    /// Only property authority may call.
+    pub fn sweep_rent_dust(ctx: Context<SweepRentDust>) -> Result<()> {
+        let property = &ctx.accounts.property_account;
+
+        let outstanding = property.rent_credited
+            .checked_sub(property.rent_paid_out).ok_or(ErrorCode::NumericOverflow)?
+            .checked_add(property.expense_funds).ok_or(ErrorCode::NumericOverflow)?
+            .checked_add(property.crank_funds).ok_or(ErrorCode::NumericOverflow)?;
+        let stranded = ctx.accounts.rent_vault_ata.amount.saturating_sub(outstanding);
+        require!(stranded > 0, ErrorCode::NothingToSweep);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.rent_vault_ata.to_account_info(),
+            to: ctx.accounts.destination_usdc.to_account_info(),
+            authority: ctx.accounts.rent_vault.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[b"rent_vault", property.fraction_mint.as_ref(), &[ctx.bumps.rent_vault]]];
+        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), stranded)?;
+
+        emit!(RentDustSwept { property: property.key(), amount: stranded });
+
+        Ok(())
+    }
+
+    /// Move a dormant holder's unclaimed rent to the `["escheat_vault", property]` PDA.
+    /// The holder is dormant once inactive for `escheat_after_secs` or
+    /// `escheat_after_epochs`; the amount stays reclaimable via `reclaim_escheated`.
+    /// Only property authority may call.
+    pub fn escheat_unclaimed(ctx: Context<EscheatUnclaimed>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        let now = Clock::get()?.unix_timestamp;
+        accrue_rent(property, now)?;
+        require!(
+            property.escheat_after_secs > 0 || property.escheat_after_epochs > 0,
+            ErrorCode::EscheatmentDisabled
+        );
+
+        let holder = &mut ctx.accounts.holder_state;
+        let dormant_by_time = property.escheat_after_secs > 0
+            && now.saturating_sub(holder.last_active_at) >= property.escheat_after_secs;
+        let dormant_by_epochs = property.escheat_after_epochs > 0
+            && property.epoch_credited.saturating_sub(holder.last_active_epoch) >= property.escheat_after_epochs;
+        require!(dormant_by_time || dormant_by_epochs, ErrorCode::HolderNotDormant);
+
+        settle_holder(property, holder)?;
+        reset_holder_debt(property, holder)?;
+
+        let amount: u64 = (holder.unclaimed / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?;
+        require!(amount > 0, ErrorCode::NothingToSweep);
+        holder.unclaimed %= SCALE;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.rent_vault_ata.to_account_info(),
+            to: ctx.accounts.escheat_vault_ata.to_account_info(),
+            authority: ctx.accounts.rent_vault.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[b"rent_vault", property.fraction_mint.as_ref(), &[ctx.bumps.rent_vault]]];
+        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), amount)?;
+        property.rent_paid_out = property.rent_paid_out.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+        property.total_escheated = property.total_escheated.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+
+        let record = &mut ctx.accounts.escheat_record;
+        record.property = property.key();
+        record.holder = ctx.accounts.holder_wallet.key();
+        record.amount = record.amount.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+        record.total_escheated = record.total_escheated.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+        record.last_escheated_at = now;
+        record.bump = ctx.bumps.escheat_record;
+
+        emit!(RentEscheated { property: property.key(), holder: record.holder, amount });
+
+        Ok(())
+    }
+
+    /// Reclaim rent previously moved to the escheat vault, less withholding from the
+    /// holder's tax profile. The payout is recorded in the holder's TaxYearRecord.
+    /// Only the holder may call.
+    pub fn reclaim_escheated(ctx: Context<ReclaimEscheated>) -> Result<()> {
+        let withholding_bps = profile_withholding_bps(&ctx.accounts.tax_profile, ctx.program_id)?;
+        let record = &mut ctx.accounts.escheat_record;
+        let amount = record.amount;
+        require!(amount > 0, ErrorCode::NothingToReclaim);
+        let withheld = bps_of(amount, withholding_bps)?;
+
+        let property = &ctx.accounts.property_account;
+        let property_key = property.key();
+        let holder_key = ctx.accounts.holder.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"escheat_vault", property_key.as_ref(), &[ctx.bumps.escheat_vault]]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escheat_vault_ata.to_account_info(),
+            to: ctx.accounts.receiver_usdc.to_account_info(),
+            authority: ctx.accounts.escheat_vault.to_account_info(),
+        };
+        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), amount - withheld)?;
+
+        let holder = &mut ctx.accounts.holder_state;
+        if withheld > 0 {
+            let tax_vault_ata = require_tax_vault(&ctx.accounts.tax_vault_ata, property, ctx.program_id)?;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.escheat_vault_ata.to_account_info(),
+                to: tax_vault_ata.to_account_info(),
+                authority: ctx.accounts.escheat_vault.to_account_info(),
+            };
+            token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), withheld)?;
+            holder.tax_withheld = holder.tax_withheld.checked_add(withheld).ok_or(ErrorCode::NumericOverflow)?;
+
+            emit!(RentWithheld {
+                property: property_key,
+                holder: holder_key,
+                tax_year: unix_year(Clock::get()?.unix_timestamp) as u16,
+                gross_amount: amount,
+                withheld,
+            });
+        }
+        mark_holder_active(property, holder)?;
+
+        record.amount = 0;
+        record.total_reclaimed = record.total_reclaimed.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+
+        record_tax_year(
+            &ctx.accounts.tax_year_record,
+            &ctx.accounts.holder,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            property_key,
+            holder_key,
+            amount,
+            withheld,
+        )?;
+
+        emit!(EscheatReclaimed { property: property_key, holder: holder_key, amount });
+
+        Ok(())
+    }
+
+    /// Appoint the property manager allowed to record and pay expenses.
+    /// Only property authority may call.
+    pub fn set_property_manager(ctx: Context<SetPropertyManager>, manager: Pubkey) -> Result<()> {
+        ctx.accounts.property_account.manager = manager;
+        Ok(())
+    }
+
+    /// Record a categorized expense against the property. The amount is
+    /// withheld from subsequent rent deposits before holders are credited.
+    /// Only the property manager may call.
+    pub fn record_expense(
+        ctx: Context<RecordExpense>,
+        category: ExpenseCategory,
+        amount: u64,
+        invoice_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        let property = &mut ctx.accounts.property_account;
+
+        let expense = &mut ctx.accounts.expense;
+        expense.property = property.key();
+        expense.id = property.expense_count;
+        expense.category = category;
+        expense.amount = amount;
+        expense.invoice_hash = invoice_hash;
+        expense.payee = ctx.accounts.payee_usdc.key();
+        expense.recorded_at = Clock::get()?.unix_timestamp;
+        expense.paid_at = 0;
+        expense.status = ExpenseStatus::Recorded;
+        expense.bump = ctx.bumps.expense;
+
+        property.expense_count = property.expense_count.checked_add(1).ok_or(ErrorCode::NumericOverflow)?;
+        property.expenses_payable = property.expenses_payable.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+
+        emit!(ExpenseRecorded {
+            property: expense.property,
+            id: expense.id,
+            category,
+            amount,
+            invoice_hash,
+        });
+
+        Ok(())
+    }
+
+    /// Pay a recorded expense to its payee out of rent withheld for expenses.
+    /// Only the property manager may call.
+    pub fn pay_expense(ctx: Context<PayExpense>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        let expense = &mut ctx.accounts.expense;
+
+        require_expense_open(expense)?;
+        require!(property.expense_funds >= expense.amount, ErrorCode::InsufficientExpenseFunds);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.rent_vault_ata.to_account_info(),
+            to: ctx.accounts.payee_usdc.to_account_info(),
+            authority: ctx.accounts.rent_vault.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[b"rent_vault", property.fraction_mint.as_ref(), &[ctx.bumps.rent_vault]]];
+        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), expense.amount)?;
+
+        property.expense_funds -= expense.amount;
+        property.total_expenses_paid = property.total_expenses_paid.checked_add(expense.amount).ok_or(ErrorCode::NumericOverflow)?;
+        expense.status = ExpenseStatus::Paid;
+        expense.paid_at = Clock::get()?.unix_timestamp;
+
+        emit!(ExpensePaid { property: property.key(), id: expense.id, amount: expense.amount });
+
+        Ok(())
+    }
+
+    /// Cancel a recorded expense. Its amount first comes off the expenses still
+    /// to be withheld; any part already withheld beyond what the remaining
+    /// expenses need is credited to holders as rent. Only the property manager
+    /// or authority may call.
+    pub fn cancel_expense(ctx: Context<CancelExpense>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        let expense = &mut ctx.accounts.expense;
+        require_expense_open(expense)?;
+
+        let unfunded = expense.amount.min(property.expenses_payable);
+        property.expenses_payable -= unfunded;
+        let released = expense.amount - unfunded;
+        property.expense_funds = property.expense_funds.checked_sub(released).ok_or(ErrorCode::NumericOverflow)?;
+
+        if released > 0 {
+            let now = Clock::get()?.unix_timestamp;
+            accrue_rent(property, now)?;
+            let scaled_amount = (released as u128).checked_mul(SCALE).ok_or(ErrorCode::NumericOverflow)?;
+            release_rent(property, scaled_amount, now)?;
+            property.rent_credited = property.rent_credited.checked_add(released).ok_or(ErrorCode::NumericOverflow)?;
+        }
+        expense.status = ExpenseStatus::Cancelled;
+
+        emit!(ExpenseCancelled { property: property.key(), id: expense.id, amount: expense.amount, released });
+
+        Ok(())
+    }
+
+    /// Set sponsor fee terms on rent: `management_fee_bps` of gross rent, plus
+    /// `performance_fee_bps` of distributions above `perf_hurdle` USDC within
+    /// each trailing `perf_window_secs` window. Only property authority may call.
+    pub fn set_rent_fees(
+        ctx: Context<SetRentFees>,
+        management_fee_bps: u16,
+        performance_fee_bps: u16,
+        perf_hurdle: u64,
+        perf_window_secs: i64,
+    ) -> Result<()> {
+        require!(management_fee_bps <= MAX_MANAGEMENT_FEE_BPS, ErrorCode::FeeTooHigh);
+        require!(performance_fee_bps <= MAX_PERFORMANCE_FEE_BPS, ErrorCode::FeeTooHigh);
+        require!(perf_window_secs >= 0, ErrorCode::InvalidAmount);
+
+        let property = &mut ctx.accounts.property_account;
+        property.management_fee_bps = management_fee_bps;
+        property.performance_fee_bps = performance_fee_bps;
+        property.perf_hurdle = perf_hurdle;
+        property.perf_window_secs = perf_window_secs;
+        property.perf_window_start = Clock::get()?.unix_timestamp;
+        property.perf_window_distributed = 0;
+        Ok(())
+    }
+
+    /// Set the primary offering price (USDC per whole fraction) used for rent
+    /// reinvestment. 0 closes the offering. Only property authority may call.
+    pub fn set_offering_price(ctx: Context<SetOfferingPrice>, price_per_fraction: u64) -> Result<()> {
+        ctx.accounts.property_account.offering_price = price_per_fraction;
+        Ok(())
+    }
+
+    /// Authorize a wallet (tenant, payment processor) to deposit rent.
+    /// Only property authority may call.
+    pub fn add_rent_payer(ctx: Context<AddRentPayer>, payer: Pubkey, label: [u8; 32]) -> Result<()> {
+        let rent_payer = &mut ctx.accounts.rent_payer;
+        rent_payer.property = ctx.accounts.property_account.key();
+        rent_payer.payer = payer;
+        rent_payer.label = label;
+        rent_payer.bump = ctx.bumps.rent_payer;
+        Ok(())
+    }
+
+    /// Revoke a rent payer and refund its account rent. Only property authority may call.
+    pub fn remove_rent_payer(_ctx: Context<RemoveRentPayer>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Set the crank reward paid to whoever runs `distribute_rent_batch`, as a
+    /// share of each payout. It is paid from `crank_funds`, never from holders' rent.
+    /// Only property authority may call.
+    pub fn set_crank_reward(ctx: Context<SetCrankReward>, crank_reward_bps: u16) -> Result<()> {
+        require!(crank_reward_bps <= MAX_CRANK_REWARD_BPS, ErrorCode::FeeTooHigh);
+        ctx.accounts.property_account.crank_reward_bps = crank_reward_bps;
+        Ok(())
+    }
+
+    /// Top up the crank reward budget. The USDC is held in the rent vault apart
+    /// from holders' rent. Anyone may call.
+    pub fn fund_crank_rewards(ctx: Context<FundCrankRewards>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_usdc.to_account_info(),
+            to: ctx.accounts.rent_vault_ata.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+
+        let property = &mut ctx.accounts.property_account;
+        property.crank_funds = property.crank_funds.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+        Ok(())
+    }
+
+    /// Withdraw unused crank reward budget. Only property authority may call.
+    pub fn withdraw_crank_funds(ctx: Context<WithdrawCrankFunds>, amount: u64) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        require!(amount > 0 && amount <= property.crank_funds, ErrorCode::InvalidAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.rent_vault_ata.to_account_info(),
+            to: ctx.accounts.destination_usdc.to_account_info(),
+            authority: ctx.accounts.rent_vault.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[b"rent_vault", property.fraction_mint.as_ref(), &[ctx.bumps.rent_vault]]];
+        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), amount)?;
+
+        property.crank_funds -= amount;
+        Ok(())
+    }
+
+    /// Set the holding period: rent credited within `holding_period_secs` of acquiring
+    /// fractions is paid pro rata to the time held when it was credited, and the rest
+    /// is redistributed to the other holders. 0 disables. Only property authority may call.
+    pub fn set_holding_period(ctx: Context<SetHoldingPeriod>, holding_period_secs: i64) -> Result<()> {
+        require!(holding_period_secs >= 0, ErrorCode::InvalidAmount);
+        ctx.accounts.property_account.holding_period_secs = holding_period_secs;
+        Ok(())
+    }
+
+    /// Set when unclaimed rent becomes escheatable: after `after_secs` without holder
+    /// activity or `after_epochs` distribution epochs. 0 disables either rule.
+    /// Only property authority may call.
+    pub fn set_escheat_policy(ctx: Context<SetEscheatPolicy>, after_secs: i64, after_epochs: u64) -> Result<()> {
+        require!(after_secs >= 0, ErrorCode::InvalidAmount);
+        let property = &mut ctx.accounts.property_account;
+        property.escheat_after_secs = after_secs;
+        property.escheat_after_epochs = after_epochs;
+        Ok(())
+    }
+
+    /// Configure the maintenance reserve: `reserve_bps` of every rent deposit
+    /// (after expenses) goes to the reserve vault until it holds `reserve_cap`
+    /// (0 = uncapped). The cap is enforced at deposit time, so the reserve only
+    /// exceeds it after the cap is lowered. Only property authority may call.
+    pub fn set_reserve_policy(ctx: Context<SetReservePolicy>, reserve_bps: u16, reserve_cap: u64) -> Result<()> {
+        require!(reserve_bps <= MAX_RESERVE_BPS, ErrorCode::FeeTooHigh);
+        let property = &mut ctx.accounts.property_account;
+        property.reserve_bps = reserve_bps;
+        property.reserve_cap = reserve_cap;
+        Ok(())
+    }
+
+    /// Request a capital expenditure drawdown from the reserve to `recipient_usdc`.
+    /// Only the property manager may call; funds move once the authority approves.
+    pub fn request_reserve_drawdown(ctx: Context<RequestReserveDrawdown>, amount: u64, purpose_hash: [u8; 32]) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        let property = &mut ctx.accounts.property_account;
+
+        let drawdown = &mut ctx.accounts.drawdown;
+        drawdown.property = property.key();
+        drawdown.id = property.drawdown_count;
+        drawdown.amount = amount;
+        drawdown.recipient = ctx.accounts.recipient_usdc.key();
+        drawdown.purpose_hash = purpose_hash;
+        drawdown.requested_at = Clock::get()?.unix_timestamp;
+        drawdown.executed_at = 0;
+        drawdown.executed = false;
+        drawdown.bump = ctx.bumps.drawdown;
+
+        property.drawdown_count = property.drawdown_count.checked_add(1).ok_or(ErrorCode::NumericOverflow)?;
+
+        Ok(())
+    }
+
+    /// Approve and execute a pending reserve drawdown. Only property authority may call.
+    pub fn approve_reserve_drawdown(ctx: Context<ApproveReserveDrawdown>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        let drawdown = &mut ctx.accounts.drawdown;
+
+        require!(!drawdown.executed, ErrorCode::DrawdownAlreadyExecuted);
+        require!(property.reserve_balance >= drawdown.amount, ErrorCode::InsufficientReserve);
+
+        let property_key = property.key();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reserve_vault_ata.to_account_info(),
+            to: ctx.accounts.recipient_usdc.to_account_info(),
+            authority: ctx.accounts.reserve_vault.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reserve_vault", property_key.as_ref(), &[ctx.bumps.reserve_vault]]];
+        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), drawdown.amount)?;
+
+        property.reserve_balance -= drawdown.amount;
+        drawdown.executed = true;
+        drawdown.executed_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    /// Move reserve funds above `reserve_cap` back to the rent vault and credit
+    /// them to holders as a special distribution. Deposits never fill the reserve
+    /// past the cap, so this only has an effect after `set_reserve_policy` lowers
+    /// it. Anyone may call.
+    pub fn release_excess_reserve(ctx: Context<ReleaseExcessReserve>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        require!(!property.supply_halted, ErrorCode::SupplyMismatch);
+        require!(property.reserve_cap > 0, ErrorCode::NothingToSweep);
+        let excess = property.reserve_balance.saturating_sub(property.reserve_cap);
+        require!(excess > 0, ErrorCode::NothingToSweep);
+
+        let now = Clock::get()?.unix_timestamp;
+        accrue_rent(property, now)?;
+
+        let property_key = property.key();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reserve_vault_ata.to_account_info(),
+            to: ctx.accounts.rent_vault_ata.to_account_info(),
+            authority: ctx.accounts.reserve_vault.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reserve_vault", property_key.as_ref(), &[ctx.bumps.reserve_vault]]];
+        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), excess)?;
+
+        property.reserve_balance -= excess;
+        credit_rent(property, (excess as u128).checked_mul(SCALE).ok_or(ErrorCode::NumericOverflow)?, now)?;
+        property.rent_credited = property.rent_credited.checked_add(excess).ok_or(ErrorCode::NumericOverflow)?;
+
+        emit!(SpecialDistribution { property: property_key, amount: excess });
+
+        Ok(())
+    }
+
+    /// Register an additional reward token for this property. Its vault is the
+    /// ATA of the `["reward_vault", property, reward_mint]` PDA, created client-side.
+    /// Only property authority may call.
+    pub fn add_reward_mint(ctx: Context<AddRewardMint>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        let mint = ctx.accounts.reward_mint.key();
+
+        require!((property.reward_count as usize) < MAX_REWARD_MINTS, ErrorCode::RewardRegistryFull);
+        require!(property.active_rewards().iter().all(|r| r.mint != mint), ErrorCode::RewardAlreadyRegistered);
+
+        let index = property.reward_count as usize;
+        property.rewards[index] = RewardStream { mint, ..RewardStream::default() };
+        property.reward_count += 1;
+
+        Ok(())
+    }
+
+    /// Deposit a registered reward token and credit it pro-rata to holders.
+    pub fn deposit_reward(ctx: Context<DepositReward>, amount: u64) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        require!(!property.supply_halted, ErrorCode::SupplyMismatch);
+        let index = property.active_rewards().iter()
+            .position(|r| r.mint == ctx.accounts.reward_mint.key())
+            .ok_or(ErrorCode::RewardNotRegistered)?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.payer_token.to_account_info(),
+            to: ctx.accounts.reward_vault_ata.to_account_info(),
+            authority: ctx.accounts.payer.to_account_info(),
+        };
+        token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+
+        let total = property.minted_fractions as u128;
+        require!(total > 0, ErrorCode::NoFractions);
+        let reward = &mut property.rewards[index];
+        let numerator = (amount as u128)
+            .checked_mul(SCALE).ok_or(ErrorCode::NumericOverflow)?
+            .checked_add(reward.undistributed_remainder).ok_or(ErrorCode::NumericOverflow)?;
+        reward.cum_reward_per_share = reward.cum_reward_per_share
+            .checked_add(numerator / total).ok_or(ErrorCode::NumericOverflow)?;
+        reward.undistributed_remainder = numerator % total;
+        reward.total_deposited = reward.total_deposited.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+
+        Ok(())
+    }
+
+    /// Claim every reward stream: USDC rent is stream 0 and is paid exactly as
+    /// `claim_rent` pays it (withholding, payout destination, tax year record),
+    /// except that DRIP holders leave it for `reinvest_rent`. `remaining_accounts`
+    /// holds, for each registered reward in registration order:
+    /// [reward_vault_ata, reward_vault PDA, holder token account].
+    pub fn claim_rewards<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimRewards<'info>>) -> Result<()> {
+        let withholding_bps = profile_withholding_bps(&ctx.accounts.tax_profile, ctx.program_id)?;
+        require_payout_destination(&ctx.accounts.holder_state, &ctx.accounts.receiver_usdc)?;
+
+        let property = &mut ctx.accounts.property_account;
+        let holder = &mut ctx.accounts.holder_state;
+        let property_key = property.key();
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
+
+        settle_holder(property, holder)?;
+        reset_holder_debt(property, holder)?;
+        mark_holder_active(property, holder)?;
+
+        if !holder.drip_enabled {
+            pay_rent(
+                ctx.program_id,
+                &ctx.accounts.token_program,
+                &ctx.accounts.system_program,
+                property,
+                holder,
+                &ctx.accounts.rent_vault,
+                &ctx.accounts.rent_vault_ata,
+                &ctx.accounts.receiver_usdc,
+                &ctx.accounts.tax_vault_ata,
+                &ctx.accounts.tax_year_record,
+                &ctx.accounts.holder,
+                withholding_bps,
+            )?;
+        }
+
+        let reward_count = property.reward_count as usize;
+        require!(ctx.remaining_accounts.len() == reward_count * 3, ErrorCode::InvalidRemainingAccounts);
+
+        for (i, accounts) in ctx.remaining_accounts.chunks(3).enumerate() {
+            let payout: u64 = (holder.reward_unclaimed[i] / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?;
+            if payout == 0 {
+                continue;
+            }
+
+            let mint = property.rewards[i].mint;
+            let (vault_ata_info, vault_info, destination_info) = (&accounts[0], &accounts[1], &accounts[2]);
+            let (vault_key, vault_bump) = Pubkey::find_program_address(
+                &[b"reward_vault", property_key.as_ref(), mint.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(vault_info.key(), vault_key, ErrorCode::InvalidRewardVault);
+            let vault_ata = Account::<TokenAccount>::try_from(vault_ata_info)?;
+            require!(vault_ata.owner == vault_key && vault_ata.mint == mint, ErrorCode::InvalidRewardVault);
+            let destination = Account::<TokenAccount>::try_from(destination_info)?;
+            require!(destination.mint == mint, ErrorCode::MintMismatch);
+            require!(destination.owner == holder.holder, ErrorCode::InvalidTokenOwner);
+
+            let cpi_accounts = Transfer {
+                from: vault_ata_info.clone(),
+                to: destination_info.clone(),
+                authority: vault_info.clone(),
+            };
+            let signer_seeds: &[&[&[u8]]] = &[&[b"reward_vault", property_key.as_ref(), mint.as_ref(), &[vault_bump]]];
+            token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), payout)?;
+
+            holder.reward_unclaimed[i] %= SCALE;
+            let reward = &mut property.rewards[i];
+            reward.total_claimed = reward.total_claimed.checked_add(payout).ok_or(ErrorCode::NumericOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    /// Burn fractions: holder burns their fraction tokens, reducing total supply.
+    /// This is typically used when closing or liquidating a property.
+    pub fn burn_fractions(ctx: Context<BurnFractions>, amount: u64) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
+        require!(property.epoch_pending == 0, ErrorCode::EpochPending);
+        let holder = &mut ctx.accounts.holder_state;
+
+        // Settle pending rewards before burning
+        settle_holder(property, holder)?;
+
+        // Update holder balance and the supply rent is divided by
+        holder.balance = holder.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+        reset_holder_debt(property, holder)?;
+        mark_holder_active(property, holder)?;
+        property.minted_fractions = property.minted_fractions.checked_sub(amount).ok_or(ErrorCode::NumericOverflow)?;
+        property.tracked_balance = property.tracked_balance.checked_sub(amount).ok_or(ErrorCode::NumericOverflow)?;
+
+        // Burn tokens via CPI
+        let cpi_accounts = token::Burn {
+            mint: ctx.accounts.fraction_mint.to_account_info(),
+            from: ctx.accounts.holder_fraction_ata.to_account_info(),
+            authority: ctx.accounts.holder.to_account_info().clone(),
+        };
+        token::burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+
+        Ok(())
+    }
+
+    /// Compare `minted_fractions` with the fraction mint's supply. A mismatch halts
+    /// rent and reward distributions until `resync_supply`. Only property authority
+    /// may call.
+    pub fn reconcile_supply(ctx: Context<ReconcileSupply>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        let mint_supply = ctx.accounts.fraction_mint.supply;
+        property.supply_halted = property.minted_fractions != mint_supply;
+
+        emit!(SupplyReconciled {
+            property: property.key(),
+            minted_fractions: property.minted_fractions,
+            mint_supply,
+            halted: property.supply_halted,
+        });
+
+        Ok(())
+    }
+
+    /// Adopt the fraction mint's supply as `minted_fractions` and lift a supply halt,
+    /// e.g. after fractions were burned outside the program. Remaining accounts are
+    /// [holder_state, holder_fraction_ata] pairs synced first, so the tracked balances
+    /// fit in the new supply. Only property authority may call.
+    pub fn resync_supply<'info>(ctx: Context<'_, '_, 'info, 'info, ResyncSupply<'info>>) -> Result<()> {
+        let property = &mut ctx.accounts.property_account;
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
+        require!(property.epoch_pending == 0, ErrorCode::EpochPending);
+        let pairs = ctx.remaining_accounts.chunks_exact(2);
+        require!(pairs.remainder().is_empty(), ErrorCode::InvalidRemainingAccounts);
+
+        let property_key = property.key();
+        for pair in pairs {
+            let (holder_info, ata_info) = (&pair[0], &pair[1]);
+            let mut holder = Account::<HolderState>::try_from(holder_info)?;
+            require!(holder.initialized, ErrorCode::InvalidHolderState);
+            let (expected_holder, _) = Pubkey::find_program_address(
+                &[b"holder", holder.holder.as_ref(), property_key.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(holder_info.key(), expected_holder, ErrorCode::InvalidHolderState);
+            require_keys_eq!(
+                ata_info.key(),
+                get_associated_token_address(&holder.holder, &property.fraction_mint),
+                ErrorCode::InvalidRemainingAccounts
+            );
+            let fraction_ata = Account::<TokenAccount>::try_from(ata_info)?;
+
+            sync_holder_balance(property, &mut holder, fraction_ata.amount)?;
+            holder.exit(ctx.program_id)?;
+        }
+
+        let mint_supply = ctx.accounts.fraction_mint.supply;
+        require!(property.tracked_balance <= mint_supply, ErrorCode::UntrackedSupplyExceeded);
+        require!(mint_supply <= property.total_fractions, ErrorCode::SupplyMismatch);
+        property.minted_fractions = mint_supply;
+        property.supply_halted = false;
+
+        emit!(SupplyReconciled {
+            property: property_key,
+            minted_fractions: mint_supply,
+            mint_supply,
+            halted: false,
+        });
+
+        Ok(())
+    }
+
+    /// Unlock NFT from escrow vault: only allowed when all fractions are burned.
+    /// Transfers the NFT back to the property authority.
+    pub fn unlock_nft(ctx: Context<UnlockNft>) -> Result<()> {
+        let property = &ctx.accounts.property_account;
+        
+        // Verify property authority is calling
+        require!(ctx.accounts.authority.key() == property.authority, ErrorCode::Unauthorized);
+
+        // Verify all fractions are burned (supply = 0)
+        let fraction_supply = ctx.accounts.fraction_mint.supply;
+        require!(fraction_supply == 0, ErrorCode::FractionsNotBurned);
+
+        // Transfer NFT from vault to authority
+        let nft_mint_key = ctx.accounts.nft_mint.key();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.nft_vault_ata.to_account_info(),
+            to: ctx.accounts.authority_nft_ata.to_account_info(),
+            authority: ctx.accounts.nft_vault.to_account_info().clone(),
+        };
+        
+        // Sign with nft_vault PDA seeds
+        let (_pda, bump) = Pubkey::find_program_address(&[b"nft_vault", nft_mint_key.as_ref()], ctx.program_id);
+        let signer_seeds: &[&[&[u8]]] = &[&[b"nft_vault", nft_mint_key.as_ref(), &[bump]]];
+        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), 1)?;
+
+        Ok(())
+    }
+
+    /// Create the global protocol config holding the trade fee schedule and treasury.
+    /// Only the program's upgrade authority may initialize it.
+    pub fn initialize_protocol_config(
+        ctx: Context<InitializeProtocolConfig>,
+        protocol_fee_bps: u16,
+        max_protocol_fee: u64,
+    ) -> Result<()> {
+        require!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, ErrorCode::FeeTooHigh);
+
+        let config = &mut ctx.accounts.protocol_config;
+        config.admin = ctx.accounts.admin.key();
+        config.treasury = ctx.accounts.treasury_usdc.key();
+        config.protocol_fee_bps = protocol_fee_bps;
+        config.max_protocol_fee = max_protocol_fee;
+        config.fee_exempt = Vec::new();
+        config.bump = ctx.bumps.protocol_config;
+        config.compliance_authority = ctx.accounts.admin.key();
+
+        Ok(())
+    }
+
+    /// Update the protocol fee rate, per-trade cap and treasury account. Admin only.
+    pub fn update_protocol_fees(
+        ctx: Context<UpdateProtocolConfig>,
+        protocol_fee_bps: u16,
+        max_protocol_fee: u64,
+    ) -> Result<()> {
+        require!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, ErrorCode::FeeTooHigh);
+
+        let config = &mut ctx.accounts.protocol_config;
+        config.treasury = ctx.accounts.treasury_usdc.key();
+        config.protocol_fee_bps = protocol_fee_bps;
+        config.max_protocol_fee = max_protocol_fee;
+
+        Ok(())
+    }
+
+    /// Add or remove a wallet from the fee-exempt list. Admin only.
+    pub fn set_fee_exemption(ctx: Context<SetFeeExemption>, wallet: Pubkey, exempt: bool) -> Result<()> {
+        let config = &mut ctx.accounts.protocol_config;
+        let existing = config.fee_exempt.iter().position(|k| *k == wallet);
+
+        match (exempt, existing) {
+            (true, None) => {
+                require!(config.fee_exempt.len() < MAX_FEE_EXEMPT, ErrorCode::FeeExemptListFull);
+                config.fee_exempt.push(wallet);
+            }
+            (false, Some(idx)) => {
+                config.fee_exempt.swap_remove(idx);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Hand the compliance role (tax profiles, remittance) to another key. Admin only.
+    pub fn set_compliance_authority(ctx: Context<SetComplianceAuthority>, compliance_authority: Pubkey) -> Result<()> {
+        ctx.accounts.protocol_config.compliance_authority = compliance_authority;
+        Ok(())
+    }
+
+    /// Create or update a holder's tax profile. Only the compliance authority may call.
+    pub fn set_tax_profile(
+        ctx: Context<SetTaxProfile>,
+        holder: Pubkey,
+        jurisdiction: [u8; 2],
+        withholding_bps: u16,
+    ) -> Result<()> {
+        require!(withholding_bps <= MAX_WITHHOLDING_BPS, ErrorCode::WithholdingTooHigh);
+
+        let profile = &mut ctx.accounts.tax_profile;
+        profile.holder = holder;
+        profile.jurisdiction = jurisdiction;
+        profile.withholding_bps = withholding_bps;
+        profile.updated_at = Clock::get()?.unix_timestamp;
+        profile.bump = ctx.bumps.tax_profile;
+
+        Ok(())
+    }
+
+    /// Remit withheld tax from a property's tax vault. Only the compliance authority may call.
+    pub fn remit_withholding(ctx: Context<RemitWithholding>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let property_key = ctx.accounts.property_account.key();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.tax_vault_ata.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.tax_vault.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[b"tax_vault", property_key.as_ref(), &[ctx.bumps.tax_vault]]];
+        token::transfer(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds), amount)?;
+
+        Ok(())
+    }
+
+    /// Enable right of first refusal: new listings are reserved for existing
+    /// holders for `window_secs` before opening to the public. 0 disables ROFR.
+    /// Only property authority may call.
+    pub fn set_rofr_window(ctx: Context<SetRofrWindow>, window_secs: i64) -> Result<()> {
+        require!(window_secs >= 0, ErrorCode::InvalidAmount);
+        ctx.accounts.property_account.rofr_window_secs = window_secs;
+        Ok(())
+    }
+
+    /// Configure streamed rent: deposits are released linearly over `period_secs`
+    /// (e.g. 30 days). 0 switches back to instant accrual; a stream already in
+    /// progress runs to its end. Only property authority may call.
+    pub fn set_rent_stream_period(ctx: Context<SetRentStreamPeriod>, period_secs: i64) -> Result<()> {
+        require!(period_secs >= 0, ErrorCode::InvalidAmount);
+        let property = &mut ctx.accounts.property_account;
+        accrue_rent(property, Clock::get()?.unix_timestamp)?;
+        property.stream_period_secs = period_secs;
+        Ok(())
+    }
+
+    /// Set the sponsor royalty charged on secondary trades of this property.
+    /// Only property authority may call.
+    pub fn set_sponsor_fee(ctx: Context<SetSponsorFee>, sponsor_fee_bps: u16) -> Result<()> {
+        require!(sponsor_fee_bps <= MAX_SPONSOR_FEE_BPS, ErrorCode::FeeTooHigh);
+        ctx.accounts.property_account.sponsor_fee_bps = sponsor_fee_bps;
+        Ok(())
+    }
+}
+
+// --------------------
+// Helpers
+// --------------------
+
+/// Total USDC cost of `fraction_amount` base units at `price_per_fraction` per
+/// whole fraction, rounded up so the seller is never short-changed.
+fn trade_cost(fraction_amount: u64, price_per_fraction: u64, fraction_decimal: u8) -> Result<u64> {
+    let unit = 10u128.checked_pow(fraction_decimal as u32).ok_or(ErrorCode::NumericOverflow)?;
+    let gross = (fraction_amount as u128).checked_mul(price_per_fraction as u128).ok_or(ErrorCode::NumericOverflow)?;
+    let cost = gross.div_ceil(unit);
+    cost.try_into().map_err(|_| error!(ErrorCode::NumericOverflow))
+}
+
+/// `amount * bps / 10_000`, rounded down.
+fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    (amount as u128)
+        .checked_mul(bps as u128).ok_or(ErrorCode::NumericOverflow)?
+        .checked_div(BPS_DENOMINATOR).ok_or(ErrorCode::NumericOverflow)?
+        .try_into().map_err(|_| error!(ErrorCode::NumericOverflow))
+}
+
+/// A recorded expense that is neither paid nor cancelled.
+fn require_expense_open(expense: &ExpenseRecord) -> Result<()> {
+    require!(expense.status != ExpenseStatus::Paid, ErrorCode::ExpenseAlreadyPaid);
+    require!(expense.status != ExpenseStatus::Cancelled, ErrorCode::ExpenseCancelled);
+    Ok(())
+}
+
+/// Credit `scaled_amount` (rent * SCALE) to current holders through
+/// `cum_rent_per_share`, carrying the division remainder to the next credit.
+fn credit_rent(property: &mut PropertyAccount, scaled_amount: u128, at: i64) -> Result<()> {
+    let total = property.minted_fractions as u128;
+    require!(total > 0, ErrorCode::NoFractions);
+    let numerator = scaled_amount
+        .checked_add(property.undistributed_remainder).ok_or(ErrorCode::NumericOverflow)?;
+    let increment = numerator.checked_div(total).ok_or(ErrorCode::NumericOverflow)?;
+    property.undistributed_remainder = numerator.checked_rem(total).ok_or(ErrorCode::NumericOverflow)?;
+    add_rent_per_share(property, increment, at)
+}
+
+/// Add `increment` to `cum_rent_per_share` as rent credited at `at`, keeping the
+/// time-weighted sum and checkpoint ring the holding period is settled against.
+fn add_rent_per_share(property: &mut PropertyAccount, increment: u128, at: i64) -> Result<()> {
+    if increment == 0 {
+        return Ok(());
+    }
+    property.cum_rent_per_share = property.cum_rent_per_share.checked_add(increment).ok_or(ErrorCode::NumericOverflow)?;
+    let weighted = increment.checked_mul(at.max(0) as u128).ok_or(ErrorCode::NumericOverflow)?;
+    property.cum_rent_time_per_share = property.cum_rent_time_per_share.checked_add(weighted).ok_or(ErrorCode::NumericOverflow)?;
+    property.record_rent_checkpoint(at);
+    Ok(())
+}
+
+/// Credit `scaled_amount` of net rent at time `at`: straight into `cum_rent_per_share`,
+/// or, in streaming mode, folded with any unreleased rent into one linear release
+/// ending at the amount-weighted average of the old end and `at + stream_period_secs`,
+/// so a small deposit barely delays rent already streaming.
+fn release_rent(property: &mut PropertyAccount, scaled_amount: u128, at: i64) -> Result<()> {
+    if property.stream_period_secs > 0 {
+        if scaled_amount == 0 {
+            return Ok(());
+        }
+        let old_remaining = if property.stream_pending > 0 { property.stream_end.saturating_sub(at).max(0) } else { 0 };
+        let pending = property.stream_pending.checked_add(scaled_amount).ok_or(ErrorCode::NumericOverflow)?;
+        let weighted_secs = property.stream_pending
+            .checked_mul(old_remaining as u128).ok_or(ErrorCode::NumericOverflow)?
+            .checked_add(scaled_amount.checked_mul(property.stream_period_secs as u128).ok_or(ErrorCode::NumericOverflow)?)
+            .ok_or(ErrorCode::NumericOverflow)?
+            / pending;
+        property.stream_pending = pending;
+        property.stream_end = at.checked_add(weighted_secs as i64).ok_or(ErrorCode::NumericOverflow)?;
+        property.stream_last_update = at;
+        Ok(())
+    } else {
+        credit_rent(property, scaled_amount, at)
+    }
+}
+
+/// Apply a deferred epoch once its record date has passed and release streamed
+/// rent up to `now`. Must run before any read of the index or change to
+/// `minted_fractions`.
+fn accrue_rent(property: &mut PropertyAccount, now: i64) -> Result<()> {
+    if property.epoch_pending > 0 && now >= property.epoch_record_date {
+        let record_date = property.epoch_record_date;
+        stream_rent(property, record_date)?;
+        let scaled_amount = property.epoch_pending;
+        property.epoch_pending = 0;
+        release_rent(property, scaled_amount, record_date)?;
+        property.epoch_credited = property.epoch_count;
+    }
+    stream_rent(property, now)
+}
+
+/// Release streamed rent accrued since the last update into `cum_rent_per_share`.
+fn stream_rent(property: &mut PropertyAccount, now: i64) -> Result<()> {
+    if property.stream_pending == 0 || now <= property.stream_last_update {
+        return Ok(());
+    }
+    // Nobody to credit yet; the backlog is released once fractions exist
+    if property.minted_fractions == 0 {
+        return Ok(());
+    }
+
+    let remaining = property.stream_end.saturating_sub(property.stream_last_update);
+    let elapsed = now.min(property.stream_end).saturating_sub(property.stream_last_update);
+    let released = if elapsed >= remaining {
+        property.stream_pending
+    } else {
+        property.stream_pending
+            .checked_mul(elapsed as u128).ok_or(ErrorCode::NumericOverflow)?
+            .checked_div(remaining as u128).ok_or(ErrorCode::NumericOverflow)?
+    };
+
+    // Released evenly over the interval, so crediting it at the midpoint keeps the
+    // time-weighted sum exact however the interval is split
+    let midpoint = property.stream_last_update + elapsed.max(0) / 2;
+    property.stream_pending = property.stream_pending.checked_sub(released).ok_or(ErrorCode::NumericOverflow)?;
+    property.stream_last_update = now;
+    credit_rent(property, released, midpoint)
+}
+
+/// Copies of a property and holder with accrual and settlement applied as of
+/// now, for view instructions. Nothing is written back.
+fn settled_view(property: &PropertyAccount, holder: &HolderState) -> Result<(PropertyAccount, HolderState)> {
+    let mut property = property.clone();
+    let mut holder = holder.clone();
+    accrue_rent(&mut property, Clock::get()?.unix_timestamp)?;
+    settle_holder(&mut property, &mut holder)?;
+    Ok((property, holder))
+}
+
+/// Return data of `get_holder_position`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct HolderPosition {
+    pub balance: u64,
+    pub minted_fractions: u64,
+    pub pending_rent: u64,
+    pub pending_rewards: [u64; MAX_REWARD_MINTS],   // Same index as PropertyAccount.rewards
+    pub pending_scrip: u64,
+    pub tax_withheld: u64,
+    pub last_settled_epoch: u64,
+}
+
+/// Return data of `quote_trade`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TradeQuote {
+    pub price: u64,
+    pub protocol_fee: u64,
+    pub sponsor_fee: u64,
+    pub seller_proceeds: u64,
+}
+
+/// USDC leg of a trade split between seller, treasury and sponsor.
+pub struct TradeFees {
+    pub protocol_fee: u64,
+    pub sponsor_fee: u64,
+    pub seller_proceeds: u64,
+}
+
+/// Compute the protocol and sponsor fees owed on a trade of `price` USDC.
+/// Trades where either counterparty is fee-exempt pay no fees.
+fn trade_fees(
+    config: &ProtocolConfig,
+    property: &PropertyAccount,
+    buyer: &Pubkey,
+    seller: &Pubkey,
+    price: u64,
+) -> Result<TradeFees> {
+    if config.fee_exempt.iter().any(|k| k == buyer || k == seller) {
+        return Ok(TradeFees { protocol_fee: 0, sponsor_fee: 0, seller_proceeds: price });
+    }
+
+    let mut protocol_fee = bps_of(price, config.protocol_fee_bps)?;
+    if config.max_protocol_fee > 0 {
+        protocol_fee = protocol_fee.min(config.max_protocol_fee);
+    }
+    let sponsor_fee = bps_of(price, property.sponsor_fee_bps)?;
+
+    let seller_proceeds = price
+        .checked_sub(protocol_fee)
+        .and_then(|v| v.checked_sub(sponsor_fee))
+        .ok_or(ErrorCode::NumericOverflow)?;
+
+    Ok(TradeFees { protocol_fee, sponsor_fee, seller_proceeds })
+}
+
+/// Pay the USDC leg of a trade from the buyer's account, which the buyer signs for.
+fn pay_trade_usdc<'info>(
+    token_program: &Program<'info, Token>,
+    buyer: &AccountInfo<'info>,
+    buyer_usdc: &Account<'info, TokenAccount>,
+    seller_usdc: &Account<'info, TokenAccount>,
+    treasury_usdc: &Account<'info, TokenAccount>,
+    sponsor_usdc: &Account<'info, TokenAccount>,
+    fees: &TradeFees,
+) -> Result<()> {
+    let legs = [
+        (seller_usdc, fees.seller_proceeds),
+        (treasury_usdc, fees.protocol_fee),
+        (sponsor_usdc, fees.sponsor_fee),
+    ];
+    for (to, amount) in legs {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = Transfer {
+            from: buyer_usdc.to_account_info(),
+            to: to.to_account_info(),
+            authority: buyer.clone(),
+        };
+        token::transfer(CpiContext::new(token_program.to_account_info(), cpi_accounts), amount)?;
+    }
+    Ok(())
+}
+
+/// Pay a settled holder's unclaimed rent from the rent vault to `receiver_usdc`,
+/// withholding `withholding_bps` into the tax vault, and add the payout to the
+/// holder's tax year record. The sub-unit remainder stays unclaimed.
+#[allow(clippy::too_many_arguments)]
+fn pay_rent<'info>(
+    program_id: &Pubkey,
+    token_program: &Program<'info, Token>,
+    system_program: &Program<'info, System>,
+    property: &mut PropertyAccount,
+    holder: &mut HolderState,
+    rent_vault: &AccountInfo<'info>,
+    rent_vault_ata: &Account<'info, TokenAccount>,
+    receiver_usdc: &Account<'info, TokenAccount>,
+    tax_vault_ata: &Option<Account<'info, TokenAccount>>,
+    tax_year_record: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    withholding_bps: u16,
+) -> Result<()> {
+    let payout: u64 = (holder.unclaimed / SCALE).try_into().map_err(|_| ErrorCode::NumericOverflow)?;
+    let withheld = bps_of(payout, withholding_bps)?;
+
+    if payout > 0 {
+        // rent_vault is a PDA; sign with its seeds
+        let (_pda, bump) = Pubkey::find_program_address(&[b"rent_vault", property.fraction_mint.as_ref()], program_id);
+        let signer_seeds: &[&[&[u8]]] = &[&[b"rent_vault", property.fraction_mint.as_ref(), &[bump]]];
+
+        let cpi_accounts = Transfer {
+            from: rent_vault_ata.to_account_info(),
+            to: receiver_usdc.to_account_info(),
+            authority: rent_vault.clone(),
+        };
+        token::transfer(CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds), payout - withheld)?;
+
+        if withheld > 0 {
+            let tax_vault_ata = require_tax_vault(tax_vault_ata, property, program_id)?;
+            let cpi_accounts = Transfer {
+                from: rent_vault_ata.to_account_info(),
+                to: tax_vault_ata.to_account_info(),
+                authority: rent_vault.clone(),
+            };
+            token::transfer(CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds), withheld)?;
+            holder.tax_withheld = holder.tax_withheld.checked_add(withheld).ok_or(ErrorCode::NumericOverflow)?;
+
+            emit!(RentWithheld {
+                property: property.property_key,
+                holder: holder.holder,
+                tax_year: unix_year(Clock::get()?.unix_timestamp) as u16,
+                gross_amount: payout,
+                withheld,
+            });
+        }
+        property.rent_paid_out = property.rent_paid_out.checked_add(payout).ok_or(ErrorCode::NumericOverflow)?;
+    }
+
+    // Keep the sub-unit remainder
+    holder.unclaimed %= SCALE;
+
+    record_tax_year(
+        tax_year_record,
+        payer,
+        system_program,
+        program_id,
+        property.property_key,
+        holder.holder,
+        payout,
+        withheld,
+    )
+}
+
+/// Move everything `holder` accrued since its last settlement (rent and each
+/// registered reward) into its unclaimed balances.
+fn settle_holder(property: &mut PropertyAccount, holder: &mut HolderState) -> Result<()> {
+    let forfeited = settle_holder_accrued(property, holder)?;
+    redistribute_forfeited(property, forfeited, holder.balance)
+}
+
+/// Settle `holder` without crediting the rent it forfeits under the holding
+/// period; returns the forfeited amount for `redistribute_forfeited`.
+fn settle_holder_accrued(property: &PropertyAccount, holder: &mut HolderState) -> Result<u128> {
+    let balance = holder.balance as u128;
+
+    let accrued = balance.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
+    let (pending, forfeited) = apply_holding_period(property, holder, accrued.saturating_sub(holder.reward_debt))?;
+    holder.unclaimed = holder.unclaimed.checked_add(pending).ok_or(ErrorCode::NumericOverflow)?;
+    if pending > 0 {
+        emit!(RentSettled {
+            property: holder.property,
+            holder: holder.holder,
+            from_epoch: holder.last_settled_epoch,
+            to_epoch: property.epoch_credited,
+            balance: holder.balance,
+            amount_scaled: pending,
+            forfeited_scaled: forfeited,
+        });
+    }
+
+    for (i, reward) in property.active_rewards().iter().enumerate() {
+        let accrued = balance.checked_mul(reward.cum_reward_per_share).ok_or(ErrorCode::NumericOverflow)?;
+        let pending = accrued.saturating_sub(holder.reward_debts[i]);
+        holder.reward_unclaimed[i] = holder.reward_unclaimed[i].checked_add(pending).ok_or(ErrorCode::NumericOverflow)?;
+    }
+
+    let accrued = balance.checked_mul(property.cum_scrip_per_share).ok_or(ErrorCode::NumericOverflow)?;
+    let pending = accrued.saturating_sub(holder.scrip_debt);
+    holder.scrip_unclaimed = holder.scrip_unclaimed.checked_add(pending).ok_or(ErrorCode::NumericOverflow)?;
+    Ok(forfeited)
+}
+
+/// Split a holder's newly settled rent under the holding period: rent credited at
+/// `t` is earned pro rata to `t - acquired_at`, so the split depends on when rent
+/// was credited, not on when it is settled. Returns `(earned, forfeited)`.
+fn apply_holding_period(property: &PropertyAccount, holder: &HolderState, pending: u128) -> Result<(u128, u128)> {
+    let period = property.holding_period_secs;
+    if period == 0 || pending == 0 || property.tracked_balance <= holder.balance {
+        return Ok((pending, 0));
+    }
+    let window_end = holder.acquired_at.saturating_add(period);
+    let balance = holder.balance as u128;
+
+    // Checkpoints split the rent since the last settlement into segments; those
+    // ending by `window_end` are exact and the one straddling it is clamped at 0.
+    let mut from = (holder.reward_debt, holder.rent_time_debt);
+    let mut forfeited = 0u128;
+    let mut crossed = false;
+    for checkpoint in property.rent_checkpoints_oldest_first() {
+        let to = (
+            balance.checked_mul(checkpoint.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?,
+            balance.checked_mul(checkpoint.cum_rent_time_per_share).ok_or(ErrorCode::NumericOverflow)?,
+        );
+        if to.0 <= from.0 {
+            continue;
+        }
+        forfeited = forfeited.checked_add(window_forfeit(from, to, window_end, period)?).ok_or(ErrorCode::NumericOverflow)?;
+        from = to;
+        if checkpoint.at >= window_end {
+            crossed = true;
+            break;
+        }
+    }
+    if !crossed {
+        let to = (
+            balance.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?,
+            balance.checked_mul(property.cum_rent_time_per_share).ok_or(ErrorCode::NumericOverflow)?,
+        );
+        forfeited = forfeited.checked_add(window_forfeit(from, to, window_end, period)?).ok_or(ErrorCode::NumericOverflow)?;
+    }
+
+    let forfeited = forfeited.min(pending);
+    Ok((pending - forfeited, forfeited))
+}
+
+/// Forfeited part of the rent accrued between two `(rent, rent * time)` points:
+/// the sum of `rent * (window_end - t) / period` over the credits in between.
+fn window_forfeit(from: (u128, u128), to: (u128, u128), window_end: i64, period: i64) -> Result<u128> {
+    let rent = to.0.saturating_sub(from.0);
+    let rent_time = to.1.saturating_sub(from.1);
+    let weighted = rent.checked_mul(window_end.max(0) as u128).ok_or(ErrorCode::NumericOverflow)?;
+    Ok(weighted.saturating_sub(rent_time) / period as u128)
+}
+
+/// Credit forfeited rent to every tracked fraction except `excluded`, the balances
+/// whose debts are reset right after settling. With nobody else to credit, it is
+/// held over to the next distribution.
+fn redistribute_forfeited(property: &mut PropertyAccount, forfeited: u128, excluded: u64) -> Result<()> {
+    if forfeited == 0 {
+        return Ok(());
+    }
+    let numerator = forfeited.checked_add(property.undistributed_remainder).ok_or(ErrorCode::NumericOverflow)?;
+    let others = property.tracked_balance.saturating_sub(excluded) as u128;
+    if others == 0 {
+        property.undistributed_remainder = numerator;
+    } else {
+        property.undistributed_remainder = numerator % others;
+        add_rent_per_share(property, numerator / others, Clock::get()?.unix_timestamp)?;
+    }
+    property.total_rent_forfeited = property.total_rent_forfeited.checked_add(forfeited).ok_or(ErrorCode::NumericOverflow)?;
+    Ok(())
+}
+
+/// Withholding rate from a holder's `TaxProfile`; no profile means no withholding.
+/// Callers must check the account is the holder's `["tax_profile", holder]` PDA.
+fn profile_withholding_bps(tax_profile: &AccountInfo, program_id: &Pubkey) -> Result<u16> {
+    if tax_profile.owner != program_id || tax_profile.data_is_empty() {
+        return Ok(0);
+    }
+    let profile = TaxProfile::try_deserialize(&mut &tax_profile.try_borrow_data()?[..])?;
+    Ok(profile.withholding_bps)
+}
+
+/// Tax vault ATA passed for a payout with withholding.
+fn require_tax_vault<'a, 'info>(
+    tax_vault_ata: &'a Option<Account<'info, TokenAccount>>,
+    property: &PropertyAccount,
+    program_id: &Pubkey,
+) -> Result<&'a Account<'info, TokenAccount>> {
+    let tax_vault_ata = tax_vault_ata.as_ref().ok_or(ErrorCode::InvalidTaxVault)?;
+    let (tax_vault, _) = Pubkey::find_program_address(&[b"tax_vault", property.property_key.as_ref()], program_id);
+    require_keys_eq!(tax_vault_ata.owner, tax_vault, ErrorCode::InvalidTaxVault);
+    require_keys_eq!(tax_vault_ata.mint, property.payment_mint, ErrorCode::MintMismatch);
+    Ok(tax_vault_ata)
+}
+
+/// Add a payout to the holder's `["tax_year", property, holder, year]` record for the
+/// current UTC year, creating the record on the first payout of the year. Nothing
+/// is created or written for a zero payout.
+#[allow(clippy::too_many_arguments)]
+fn record_tax_year<'info>(
+    record_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    property: Pubkey,
+    holder: Pubkey,
+    gross: u64,
+    withheld: u64,
+) -> Result<()> {
+    if gross == 0 {
+        return Ok(());
+    }
+    let year = unix_year(Clock::get()?.unix_timestamp) as u16;
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"tax_year", property.as_ref(), holder.as_ref(), &year.to_le_bytes()],
+        program_id,
+    );
+    require_keys_eq!(record_info.key(), expected, ErrorCode::InvalidTaxYear);
+
+    let mut record = if record_info.owner == program_id {
+        TaxYearRecord::try_deserialize(&mut &record_info.try_borrow_data()?[..])?
+    } else {
+        let space = TaxYearRecord::LEN;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"tax_year", property.as_ref(), holder.as_ref(), &year.to_le_bytes(), &[bump]]];
+        let lamports = Rent::get()?.minimum_balance(space).saturating_sub(record_info.lamports());
+        if lamports > 0 {
+            let cpi_accounts = system_program::Transfer { from: payer.clone(), to: record_info.clone() };
+            system_program::transfer(CpiContext::new(system_program.clone(), cpi_accounts), lamports)?;
+        }
+        let cpi_accounts = system_program::Allocate { account_to_allocate: record_info.clone() };
+        system_program::allocate(CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds), space as u64)?;
+        let cpi_accounts = system_program::Assign { account_to_assign: record_info.clone() };
+        system_program::assign(CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds), program_id)?;
+        TaxYearRecord { property, holder, year, gross_paid: 0, withheld: 0, bump }
+    };
+
+    record.gross_paid = record.gross_paid.checked_add(gross).ok_or(ErrorCode::NumericOverflow)?;
+    record.withheld = record.withheld.checked_add(withheld).ok_or(ErrorCode::NumericOverflow)?;
+    record.try_serialize(&mut &mut record_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+/// UTC calendar year of a unix timestamp (civil-from-days).
+fn unix_year(ts: i64) -> i64 {
+    let z = ts.div_euclid(86_400) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let year = yoe + era * 400;
+    if mp >= 10 { year + 1 } else { year }
+}
+
+/// Reset reward debts to the holder's current balance at the current indices.
+fn reset_holder_debt(property: &PropertyAccount, holder: &mut HolderState) -> Result<()> {
+    let balance = holder.balance as u128;
+    holder.reward_debt = balance.checked_mul(property.cum_rent_per_share).ok_or(ErrorCode::NumericOverflow)?;
+    holder.rent_time_debt = balance.checked_mul(property.cum_rent_time_per_share).ok_or(ErrorCode::NumericOverflow)?;
+    for (i, reward) in property.active_rewards().iter().enumerate() {
+        holder.reward_debts[i] = balance.checked_mul(reward.cum_reward_per_share).ok_or(ErrorCode::NumericOverflow)?;
+    }
+    holder.scrip_debt = balance.checked_mul(property.cum_scrip_per_share).ok_or(ErrorCode::NumericOverflow)?;
+    holder.last_settled_epoch = property.epoch_credited;
+    Ok(())
+}
+
+/// Rent must go to the holder's payout destination, or to a token account the
+/// holder owns when none is set.
+fn require_payout_destination(holder: &HolderState, receiver: &Account<TokenAccount>) -> Result<()> {
+    if holder.payout_destination != Pubkey::default() {
+        require_keys_eq!(receiver.key(), holder.payout_destination, ErrorCode::InvalidPayoutDestination);
+    } else {
+        require_keys_eq!(receiver.owner, holder.holder, ErrorCode::InvalidPayoutDestination);
+    }
+    Ok(())
+}
+
+/// Set the identity of a newly created holder state and start its activity
+/// clock; no-op once initialized.
+fn init_holder(holder: &mut HolderState, wallet: Pubkey, property: &PropertyAccount, bump: u8) -> Result<()> {
+    if !holder.initialized {
+        holder.holder = wallet;
+        holder.property = property.property_key;
+        holder.bump = bump;
+        holder.initialized = true;
+        mark_holder_active(property, holder)?;
+    }
+    Ok(())
+}
+
+/// Record activity for the escheatment policy. Only called for actions the holder signed.
+fn mark_holder_active(property: &PropertyAccount, holder: &mut HolderState) -> Result<()> {
+    holder.last_active_at = Clock::get()?.unix_timestamp;
+    holder.last_active_epoch = property.epoch_credited;
+    Ok(())
+}
+
+/// Add `amount` to a holder's balance, moving `acquired_at` to the
+/// balance-weighted average of the existing balance's time and now.
+fn add_holder_balance(holder: &mut HolderState, amount: u64, now: i64) -> Result<()> {
+    let balance = holder.balance.checked_add(amount).ok_or(ErrorCode::NumericOverflow)?;
+    if balance > 0 {
+        let weighted = (holder.balance as i128 * holder.acquired_at as i128 + amount as i128 * now as i128) / balance as i128;
+        holder.acquired_at = weighted as i64;
+    }
+    holder.balance = balance;
+    Ok(())
+}
+
+/// Settle `holder` and set its balance to `ata_amount` plus what it has in
+/// listing escrow. Increases must fit in the minted supply not tracked by any holder state.
+fn sync_holder_balance(property: &mut PropertyAccount, holder: &mut HolderState, ata_amount: u64) -> Result<()> {
+    settle_holder(property, holder)?;
+    let ata_amount = ata_amount.checked_add(holder.listed).ok_or(ErrorCode::NumericOverflow)?;
+    if ata_amount >= holder.balance {
+        let increase = ata_amount - holder.balance;
+        let tracked = property.tracked_balance.checked_add(increase).ok_or(ErrorCode::NumericOverflow)?;
+        require!(tracked <= property.minted_fractions, ErrorCode::UntrackedSupplyExceeded);
+        property.tracked_balance = tracked;
+        add_holder_balance(holder, increase, Clock::get()?.unix_timestamp)?;
+    } else {
+        property.tracked_balance = property.tracked_balance.checked_sub(holder.balance - ata_amount).ok_or(ErrorCode::NumericOverflow)?;
+        holder.balance = ata_amount;
+    }
+    reset_holder_debt(property, holder)
+}
+
+/// Settle pending rewards on both holders, then move `amount` of tracked
+/// balance from `from` to `to` and reset their debts at the current indices.
+/// Rent either side forfeits goes to holders other than the two.
+fn move_holder_balance(
+    property: &mut PropertyAccount,
+    from: &mut HolderState,
+    to: &mut HolderState,
+    amount: u64,
+) -> Result<()> {
+    let forfeited = settle_holder_accrued(property, from)?
+        .checked_add(settle_holder_accrued(property, to)?)
+        .ok_or(ErrorCode::NumericOverflow)?;
+    let excluded = from.balance.checked_add(to.balance).ok_or(ErrorCode::NumericOverflow)?;
+    redistribute_forfeited(property, forfeited, excluded)?;
+
+    from.balance = from.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+    add_holder_balance(to, amount, Clock::get()?.unix_timestamp)?;
+
+    reset_holder_debt(property, from)?;
+    reset_holder_debt(property, to)
+}
+
+/// With ROFR enabled, sales outside of a listing are limited to existing holders.
+fn require_rofr_direct_sale(property: &PropertyAccount, buyer_holder: &HolderState) -> Result<()> {
+    if property.rofr_window_secs > 0 {
+        require!(buyer_holder.balance > 0, ErrorCode::RofrRequiresListing);
+    }
+    Ok(())
+}
+
+/// Verify that the instruction immediately preceding the current one is an
+/// ed25519 program instruction checking a single signature by `signer` over `message`.
+fn verify_ed25519_signature(instructions_sysvar: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, ErrorCode::MissingSignatureVerification);
+    let ix = load_instruction_at_checked((current - 1) as usize, instructions_sysvar)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::MissingSignatureVerification);
+
+    // Layout: num_signatures (1), padding (1), then one Ed25519SignatureOffsets (7 x u16)
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidSignatureVerification);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_ix = read_u16(4);
+    let pubkey_offset = read_u16(6) as usize;
+    let pubkey_ix = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix = read_u16(14);
+
+    // All offsets must point into the ed25519 instruction itself
+    require!(
+        signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+        ErrorCode::InvalidSignatureVerification
+    );
+
+    let signed_pubkey = data.get(pubkey_offset..pubkey_offset + 32).ok_or(ErrorCode::InvalidSignatureVerification)?;
+    let signed_message = data.get(message_offset..message_offset + message_size).ok_or(ErrorCode::InvalidSignatureVerification)?;
+    require!(signed_pubkey == signer.as_ref(), ErrorCode::InvalidSignatureVerification);
+    require!(signed_message == message, ErrorCode::InvalidSignatureVerification);
+
+    Ok(())
+}
+
+// --------------------
+// Accounts and Contexts
+// --------------------
+
+#[account]
+pub struct PropertyAccount {
+    pub property_key: Pubkey,
+    pub authority: Pubkey,
+    pub nft_mint: Pubkey,
+    pub fraction_mint: Pubkey,
+    pub total_fractions: u64,
+    pub minted_fractions: u64,  // Track actual circulating supply
+    pub fraction_decimal: u8,
+    pub cum_rent_per_share: u128,
+    pub bump: u8,
+    pub metadata_uri: [u8; 200],  // Fixed size for IPFS/Arweave URIs
+    pub sponsor_fee_bps: u16,     // Royalty to the authority on secondary trades
+    pub rofr_window_secs: i64,    // Holder-only window on new listings, 0 = ROFR disabled
+    pub undistributed_remainder: u128,  // Scaled rent left over from the last cum_rent_per_share division
+    pub rent_credited: u64,       // Total rent credited to holders
+    pub rent_paid_out: u64,       // Total rent paid out of the rent vault to holders
+    pub stream_period_secs: i64,  // Linear release period for deposits, 0 = instant accrual
+    pub stream_pending: u128,     // Scaled rent deposited but not yet released
+    pub stream_end: i64,
+    pub stream_last_update: i64,
+    pub reward_count: u8,         // Number of registered entries in `rewards`
+    pub rewards: [RewardStream; MAX_REWARD_MINTS],  // Additional reward tokens beyond USDC rent
+    pub manager: Pubkey,          // Records and pays property expenses
+    pub expense_count: u64,
+    pub expenses_payable: u64,    // Recorded expenses not yet withheld from rent
+    pub expense_funds: u64,       // Rent withheld in the vault for unpaid expenses
+    pub total_expenses_paid: u64,
+    pub reserve_bps: u16,         // Share of rent routed to the maintenance reserve
+    pub reserve_cap: u64,         // Reserve target; excess flows back to holders, 0 = uncapped
+    pub reserve_balance: u64,
+    pub drawdown_count: u64,
+    pub management_fee_bps: u16,  // Sponsor fee on gross rent
+    pub performance_fee_bps: u16, // Sponsor fee on distributions above the hurdle
+    pub perf_hurdle: u64,         // Distributions per window before the performance fee applies
+    pub perf_window_secs: i64,    // Trailing window length, 0 = a single never-resetting window
+    pub perf_window_start: i64,
+    pub perf_window_distributed: u64,
+    pub crank_reward_bps: u16,    // Share of pushed payouts paid to the crank caller
+    pub crank_funds: u64,         // Sponsor-funded crank rewards held in the rent vault
+    pub epoch_count: u64,         // Distribution epochs opened, one per deposit
+    pub epoch_credited: u64,      // Epochs whose record date has passed
+    pub epoch_pending: u128,      // Scaled rent of the epoch awaiting its record date
+    pub epoch_record_date: i64,
+    pub escheat_after_secs: i64,  // 0 = no time-based escheatment
+    pub escheat_after_epochs: u64, // 0 = no epoch-based escheatment
+    pub total_escheated: u64,
+    pub supply_halted: bool,      // Set by reconcile_supply when minted_fractions != mint supply
+    pub payment_mint: Pubkey,     // Mint rent is paid in (USDC)
+    pub offering_price: u64,      // USDC per whole fraction for reinvestment, 0 = closed
+    pub cum_scrip_per_share: u128, // Scrip fractions per fraction, scaled by SCALE
+    pub scrip_remainder: u128,
+    pub scrip_reserved: u64,      // Declared scrip not yet claimed; counts against total_fractions
+    pub tracked_balance: u64,     // Sum of all HolderState balances
+    pub holding_period_secs: i64, // Rent eligibility ramps up over this long after acquisition, 0 = off
+    pub total_rent_forfeited: u128, // Scaled rent forfeited under the holding period
+    pub payment_decimals: u8,     // Decimals of payment_mint
+    pub cum_rent_time_per_share: u128, // Sum of each cum_rent_per_share increment times its credit time
+    pub rent_checkpoints: [RentCheckpoint; RENT_CHECKPOINTS],  // Ring of recent credits
+    pub rent_checkpoint_head: u8,
+    pub rent_checkpoint_len: u8,
+}
+
+impl PropertyAccount {
+    pub const LEN: usize = 8
+        + 32 + 32 + 32 + 32        // property_key, authority, nft_mint, fraction_mint
+        + 8 + 8 + 1 + 16 + 1       // total/minted fractions, fraction_decimal, cum_rent_per_share, bump
+        + 4 + 200                  // metadata_uri
+        + 2 + 8                    // sponsor_fee_bps, rofr_window_secs
+        + 16 + 8 + 8               // undistributed_remainder, rent_credited, rent_paid_out
+        + 8 + 16 + 8 + 8           // stream_period_secs, stream_pending, stream_end, stream_last_update
+        + 1 + RewardStream::LEN * MAX_REWARD_MINTS
+        + 32 + 8 + 8 + 8 + 8       // manager, expense_count, expenses_payable, expense_funds, total_expenses_paid
+        + 2 + 8 + 8 + 8            // reserve_bps, reserve_cap, reserve_balance, drawdown_count
+        + 2 + 2 + 8 + 8 + 8 + 8    // management/performance fee terms and trailing window
+        + 2 + 8                    // crank_reward_bps, crank_funds
+        + 8 + 8 + 16 + 8           // epoch_count, epoch_credited, epoch_pending, epoch_record_date
+        + 8 + 8 + 8                // escheat_after_secs, escheat_after_epochs, total_escheated
+        + 1                        // supply_halted
+        + 32                       // payment_mint
+        + 8                        // offering_price
+        + 16 + 16 + 8              // cum_scrip_per_share, scrip_remainder, scrip_reserved
+        + 8                        // tracked_balance
+        + 8 + 16                   // holding_period_secs, total_rent_forfeited
+        + 1                        // payment_decimals
+        + 16                       // cum_rent_time_per_share
+        + RentCheckpoint::LEN * RENT_CHECKPOINTS + 1 + 1;
+
+    /// Registered reward streams, in registration order.
+    pub fn active_rewards(&self) -> &[RewardStream] {
+        &self.rewards[..self.reward_count as usize]
+    }
+
+    /// Snapshot the rent accumulators as of `at`. Credits closer together than
+    /// 1/RENT_CHECKPOINTS of the holding period share a checkpoint, so the ring
+    /// spans at least one period.
+    fn record_rent_checkpoint(&mut self, at: i64) {
+        let checkpoint = RentCheckpoint {
+            at,
+            cum_rent_per_share: self.cum_rent_per_share,
+            cum_rent_time_per_share: self.cum_rent_time_per_share,
+        };
+        if self.rent_checkpoint_len > 0 {
+            let newest = self.rent_checkpoints[self.rent_checkpoint_head as usize];
+            if at.saturating_sub(newest.at) < (self.holding_period_secs / RENT_CHECKPOINTS as i64).max(1) {
+                self.rent_checkpoints[self.rent_checkpoint_head as usize] = RentCheckpoint { at: at.max(newest.at), ..checkpoint };
+                return;
+            }
+            self.rent_checkpoint_head = ((self.rent_checkpoint_head as usize + 1) % RENT_CHECKPOINTS) as u8;
+        }
+        self.rent_checkpoint_len = (self.rent_checkpoint_len + 1).min(RENT_CHECKPOINTS as u8);
+        self.rent_checkpoints[self.rent_checkpoint_head as usize] = checkpoint;
+    }
+
+    fn rent_checkpoints_oldest_first(&self) -> impl Iterator<Item = &RentCheckpoint> {
+        let len = self.rent_checkpoint_len as usize;
+        (0..len).rev().map(move |i| &self.rent_checkpoints[(self.rent_checkpoint_head as usize + RENT_CHECKPOINTS - i) % RENT_CHECKPOINTS])
+    }
+}
+
+/// Rent accumulators as of the latest credit at or before `at`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RentCheckpoint {
+    pub at: i64,
+    pub cum_rent_per_share: u128,
+    pub cum_rent_time_per_share: u128,
+}
+
+impl RentCheckpoint {
+    pub const LEN: usize = 8 + 16 + 16;
+}
+
+/// An additional reward token paid pro-rata to holders from its own vault PDA.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RewardStream {
+    pub mint: Pubkey,
+    pub cum_reward_per_share: u128,
+    pub undistributed_remainder: u128,
+    pub total_deposited: u64,
+    pub total_claimed: u64,
+}
+
+impl RewardStream {
+    pub const LEN: usize = 32 + 16 + 16 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExpenseCategory {
+    Maintenance,
+    Tax,
+    Insurance,
+    Utilities,
+    Management,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExpenseStatus {
+    Recorded,
+    Paid,
+    Cancelled,
+}
+
+/// Expense ledger entry. seeds: ["expense", property, id]
+#[account]
+pub struct ExpenseRecord {
+    pub property: Pubkey,
+    pub id: u64,
+    pub category: ExpenseCategory,
+    pub amount: u64,
+    pub invoice_hash: [u8; 32],   // Hash of the off-chain invoice document
+    pub payee: Pubkey,            // USDC token account to pay
+    pub recorded_at: i64,
+    pub paid_at: i64,
+    pub status: ExpenseStatus,
+    pub bump: u8,
+}
+
+/// Manager request to draw on the maintenance reserve. seeds: ["drawdown", property, id]
+#[account]
+pub struct ReserveDrawdown {
+    pub property: Pubkey,
+    pub id: u64,
+    pub amount: u64,
+    pub recipient: Pubkey,        // USDC token account to pay
+    pub purpose_hash: [u8; 32],   // Hash of the off-chain capex justification
+    pub requested_at: i64,
+    pub executed_at: i64,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+/// One rent deposit: holders of record at `record_date` share `net_amount` at
+/// `rate_per_share` (scaled by SCALE). seeds: ["epoch", property, id]
+#[account]
+pub struct DistributionEpoch {
+    pub property: Pubkey,
+    pub id: u64,
+    pub record_date: i64,
+    pub opened_at: i64,
+    pub net_amount: u64,
+    pub supply: u64,              // minted_fractions the rate is based on
+    pub rate_per_share: u128,
+    pub payer: Pubkey,
+    pub reference: [u8; 32],      // Lease id, period or other bookkeeping reference
+    pub bump: u8,
+}
+
+/// Wallet allowed to deposit rent for a property. seeds: ["rent_payer", property, payer]
+#[account]
+pub struct RentPayer {
+    pub property: Pubkey,
+    pub payer: Pubkey,
+    pub label: [u8; 32],          // e.g. tenant or lease identifier
+    pub bump: u8,
+}
+
+/// Rent escheated from a dormant holder, reclaimable by them.
+/// seeds: ["escheat", property, holder]
+#[account]
+pub struct EscheatRecord {
+    pub property: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,              // Currently reclaimable
+    pub total_escheated: u64,
+    pub total_reclaimed: u64,
+    pub last_escheated_at: i64,
+    pub bump: u8,
+}
+
+/// Per-wallet withholding profile set by the compliance authority. seeds: ["tax_profile", holder]
+#[account]
+pub struct TaxProfile {
+    pub holder: Pubkey,
+    pub jurisdiction: [u8; 2],    // ISO 3166-1 alpha-2 country code
+    pub withholding_bps: u16,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// Rent paid and tax withheld for one holder, property and year.
+/// seeds: ["tax_year", property, holder, year]
+#[account]
+pub struct TaxYearRecord {
+    pub property: Pubkey,
+    pub holder: Pubkey,
+    pub year: u16,
+    pub gross_paid: u64,
+    pub withheld: u64,
+    pub bump: u8,
+}
+
+impl TaxYearRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 8 + 8 + 1;
+}
+
+/// On-chain ask for a seller's fractions, held in the listing PDA's escrow ATA.
+#[account]
+pub struct Listing {
+    pub property: Pubkey,
+    pub seller: Pubkey,
+    pub fraction_amount: u64,     // Remaining amount for sale
+    pub price_per_fraction: u64,  // USDC per whole fraction
+    pub created_at: i64,
+    pub rofr_ends_at: i64,        // Only existing holders may fill before this time
+    pub valid_until: i64,
+    pub bump: u8,
+}
+
+/// A trade price observation; `cum_price_seconds` is the running integral of
+/// the last trade price over time up to `timestamp`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PriceObservation {
+    pub timestamp: i64,
+    pub price_per_fraction: u64,
+    pub cum_price_seconds: u128,
+}
+
+/// Per-property ring buffer of marketplace fills used as a reference price.
+#[account]
+pub struct PriceOracle {
+    pub property: Pubkey,
+    pub last_price: u64,          // USDC per whole fraction at the latest fill of at least one whole USDC
+    pub last_trade_at: i64,
+    pub total_volume: u64,        // Fraction base units traded
+    pub total_quote_volume: u64,  // USDC traded
+    pub trade_count: u64,
+    pub head: u16,                // Index of the newest observation
+    pub len: u16,                 // Number of populated observations
+    pub observations: [PriceObservation; PRICE_OBSERVATIONS],
+    pub bump: u8,
+}
+
+impl PriceOracle {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 2 + 2 + (8 + 8 + 16) * PRICE_OBSERVATIONS + 1;
+
+    /// Running price integral extrapolated to `now` at the last trade price.
+    fn cum_price_seconds_at(&self, now: i64) -> Result<u128> {
+        if self.len == 0 {
+            return Ok(0);
+        }
+        let newest = &self.observations[self.head as usize];
+        let elapsed = now.checked_sub(newest.timestamp).ok_or(ErrorCode::NumericOverflow)?.max(0) as u128;
+        let since = (newest.price_per_fraction as u128).checked_mul(elapsed).ok_or(ErrorCode::NumericOverflow)?;
+        newest.cum_price_seconds.checked_add(since).ok_or(error!(ErrorCode::NumericOverflow))
+    }
+
+    /// Record a marketplace fill. Fills below one whole payment token only
+    /// count toward volume; larger fills within `MIN_OBSERVATION_INTERVAL_SECS`
+    /// of the newest observation update `last_price` but don't take a slot.
+    pub fn record_fill(
+        &mut self,
+        property: Pubkey,
+        now: i64,
+        price_per_fraction: u64,
+        fraction_amount: u64,
+        quote_amount: u64,
+        payment_decimals: u8,
+    ) -> Result<()> {
+        if self.property == Pubkey::default() {
+            self.property = property;
+        }
+
+        let min_notional = 10u64.checked_pow(payment_decimals as u32).ok_or(ErrorCode::NumericOverflow)?;
+        if quote_amount >= min_notional {
+            let due = self.len == 0
+                || now.saturating_sub(self.observations[self.head as usize].timestamp) >= MIN_OBSERVATION_INTERVAL_SECS;
+            if due {
+                let cum_price_seconds = self.cum_price_seconds_at(now)?;
+                if self.len > 0 {
+                    self.head = ((self.head as usize + 1) % PRICE_OBSERVATIONS) as u16;
+                }
+                self.len = (self.len + 1).min(PRICE_OBSERVATIONS as u16);
+                self.observations[self.head as usize] = PriceObservation { timestamp: now, price_per_fraction, cum_price_seconds };
+            }
+            self.last_price = price_per_fraction;
+        }
+
+        self.last_trade_at = now;
+        self.total_volume = self.total_volume.checked_add(fraction_amount).ok_or(ErrorCode::NumericOverflow)?;
+        self.total_quote_volume = self.total_quote_volume.checked_add(quote_amount).ok_or(ErrorCode::NumericOverflow)?;
+        self.trade_count = self.trade_count.checked_add(1).ok_or(ErrorCode::NumericOverflow)?;
+        Ok(())
+    }
+
+    /// Time-weighted average price over `[now - window_secs, now]`.
+    pub fn twap(&self, now: i64, window_secs: i64) -> Result<u64> {
+        require!(window_secs > 0, ErrorCode::InvalidAmount);
+        let start = now.checked_sub(window_secs).ok_or(ErrorCode::NumericOverflow)?;
+
+        // Newest observation at or before the window start; the price is constant
+        // between observations so the integral at `start` is exact.
+        let anchor_obs = (0..self.len as usize)
+            .map(|i| &self.observations[(self.head as usize + PRICE_OBSERVATIONS - i) % PRICE_OBSERVATIONS])
+            .find(|obs| obs.timestamp <= start)
+            .ok_or(ErrorCode::InsufficientPriceHistory)?;
+        let lead = (start - anchor_obs.timestamp) as u128;
+        let cum_start = anchor_obs.cum_price_seconds
+            .checked_add((anchor_obs.price_per_fraction as u128).checked_mul(lead).ok_or(ErrorCode::NumericOverflow)?)
+            .ok_or(ErrorCode::NumericOverflow)?;
+
+        let cum_now = self.cum_price_seconds_at(now)?;
+        let twap = cum_now.checked_sub(cum_start).ok_or(ErrorCode::NumericOverflow)? / window_secs as u128;
+        twap.try_into().map_err(|_| error!(ErrorCode::NumericOverflow))
+    }
+}
+
+/// Marks a signed sell order nonce as filled so the order cannot be replayed.
+#[account]
+pub struct OrderNonce {
+    pub seller: Pubkey,
+    pub nonce: u64,
+    pub filled_at: i64,
+    pub bump: u8,
+}
+
+/// Sell order signed off-chain by the seller with their wallet key.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SellOrder {
+    pub property: Pubkey,
+    pub seller: Pubkey,
+    pub fraction_amount: u64,
+    pub price_per_fraction: u64,  // USDC per whole fraction
+    pub expiry: i64,
+    pub nonce: u64,
+}
+
+/// Global protocol settings: trade fee schedule, treasury and fee-exempt wallets.
+#[account]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,          // Treasury USDC token account receiving protocol fees
+    pub protocol_fee_bps: u16,
+    pub max_protocol_fee: u64,     // Per-trade cap on the protocol fee, 0 = uncapped
+    pub fee_exempt: Vec<Pubkey>,   // Up to MAX_FEE_EXEMPT wallets
+    pub bump: u8,
+    pub compliance_authority: Pubkey, // Manages tax profiles and remits withholding
+}
+
+#[account]
+pub struct HolderState {
+    pub holder: Pubkey,
+    pub property: Pubkey,
+    pub balance: u64,
+    pub reward_debt: u128,
+    pub unclaimed: u128,
+    pub bump: u8,
+    pub reward_debts: [u128; MAX_REWARD_MINTS],      // Per registered reward, same index as PropertyAccount.rewards
+    pub reward_unclaimed: [u128; MAX_REWARD_MINTS],
+    pub tax_withheld: u64,        // Cumulative rent withheld for tax
+    pub last_settled_epoch: u64,  // Epochs before this one are included in reward_debt
+    pub last_active_at: i64,      // Last holder-signed action, for escheatment
+    pub drip_enabled: bool,       // Reinvest rent into fractions via reinvest_rent
+    pub scrip_debt: u128,
+    pub scrip_unclaimed: u128,
+    pub initialized: bool,        // Distinguishes a registered zero-balance holder from a fresh account
+    pub payout_destination: Pubkey, // Token account rent is paid to, default = any owned by holder
+    pub claim_delegate: Pubkey,   // May trigger claim_rent, default = none
+    pub acquired_at: i64,         // Balance-weighted acquisition time, for the holding period
+    pub listed: u64,              // Part of balance held in listing escrow
+    pub last_active_epoch: u64,   // epoch_credited at the last holder-signed action
+    pub drip_max_price: u64,      // Highest offering price reinvest_rent may buy at
+    pub rent_time_debt: u128,     // balance * cum_rent_time_per_share at the last settlement
+}
+
+impl HolderState {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 16 + 16 + 1 + 16 * MAX_REWARD_MINTS * 2 + 8 + 8 + 8 + 1 + 16 + 16 + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 16;
+}
+
+#[derive(Accounts)]
+pub struct InitializeProperty<'info> {
+    #[account(init, payer = authority, space = PropertyAccount::LEN)]
+    pub property_account: Account<'info, PropertyAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: NFT mint pubkey reference
+    pub nft_mint: AccountInfo<'info>,
+
+    /// CHECK: Fraction mint (SPL) account to be created via CPI - must be signer for system create_account
+    #[account(mut, signer)]
+    pub fraction_mint: AccountInfo<'info>,
+
+    /// CHECK: Mint authority PDA for the fraction mint
+    /// seeds: ["fraction_authority", property_account]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Rent vault PDA
+    pub rent_vault: UncheckedAccount<'info>,
+    /// CHECK: NFT vault PDA
+    pub nft_vault: UncheckedAccount<'info>,
+
+    /// Associated token accounts (should be created client-side)
     /// CHECK: rent_vault_ata
     pub rent_vault_ata: UncheckedAccount<'info>,
     /// CHECK: nft_vault_ata
     pub nft_vault_ata: UncheckedAccount<'info>,
 
-    /// The USDC mint used for rent collection
-    /// CHECK: USDC mint address - validated by client
-    pub usdc_mint: AccountInfo<'info>,
+    /// The USDC mint used for rent collection
+    pub usdc_mint: Box<Account<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct DepositNftIntoVault<'info> {
+    #[account(mut, has_one = nft_mint)]
+    pub property_account: Account<'info, PropertyAccount>,
+
+    /// CHECK: NFT mint
+    pub nft_mint: AccountInfo<'info>,
+
+    /// Token account holding the NFT (owned by nft_vault PDA)
+    #[account(mut)]
+    pub nft_vault_ata: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct MintFractions<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    #[account(mut)]
+    pub fraction_mint: Account<'info, Mint>,
+    /// CHECK: mint authority PDA
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = HolderState::LEN,
+        seeds = [b"holder", destination.owner.as_ref(), property_account.key().as_ref()],
+        bump
+    )]
+    pub destination_holder: Account<'info, HolderState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterHolder<'info> {
+    #[account(mut)]
+    pub property_account: Account<'info, PropertyAccount>,
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(
+        init,
+        payer = holder,
+        space = HolderState::LEN,
+        seeds = [b"holder", holder.key().as_ref(), property_account.key().as_ref()],
+        bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    #[account(
+        associated_token::mint = property_account.fraction_mint,
+        associated_token::authority = holder
+    )]
+    pub holder_fraction_ata: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseHolderState<'info> {
+    #[account(mut)]
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(
+        mut,
+        close = holder,
+        seeds = [b"holder", holder.key().as_ref(), property_account.key().as_ref()],
+        bump = holder_state.bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+}
+
+#[derive(Accounts)]
+pub struct SyncHolder<'info> {
+    #[account(mut)]
+    pub property_account: Account<'info, PropertyAccount>,
+    /// CHECK: wallet of the holder being synced; only used for PDA and ATA derivation
+    pub holder_wallet: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"holder", holder_wallet.key().as_ref(), property_account.key().as_ref()],
+        bump = holder_state.bump,
+        constraint = holder_state.initialized @ ErrorCode::InvalidHolderState
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    #[account(
+        associated_token::mint = property_account.fraction_mint,
+        associated_token::authority = holder_wallet
+    )]
+    pub holder_fraction_ata: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct BuyFractions<'info> {
+    #[account(mut)]
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = buyer_usdc.owner == buyer.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = buyer_usdc.mint == seller_usdc.mint @ ErrorCode::MintMismatch
+    )]
+    pub buyer_usdc: Account<'info, TokenAccount>,
+    #[account(mut, constraint = seller_usdc.owner == seller.key() @ ErrorCode::InvalidTokenOwner)]
+    pub seller_usdc: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = buyer_fraction_ata.owner == buyer.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = buyer_fraction_ata.mint == property_account.fraction_mint @ ErrorCode::MintMismatch
+    )]
+    pub buyer_fraction_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = seller_fraction_ata.owner == seller.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = seller_fraction_ata.mint == property_account.fraction_mint @ ErrorCode::MintMismatch
+    )]
+    pub seller_fraction_ata: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    #[account(mut, address = protocol_config.treasury, constraint = treasury_usdc.mint == buyer_usdc.mint)]
+    pub treasury_usdc: Account<'info, TokenAccount>,
+    #[account(mut, constraint = sponsor_usdc.owner == property_account.authority @ ErrorCode::Unauthorized, constraint = sponsor_usdc.mint == buyer_usdc.mint)]
+    pub sponsor_usdc: Account<'info, TokenAccount>,
+
+    #[account(init_if_needed, payer = buyer, space = PriceOracle::LEN, seeds = [b"price_oracle", property_account.key().as_ref()], bump)]
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+
+    #[account(init_if_needed, payer = buyer, space = HolderState::LEN, seeds = [b"holder", buyer.key().as_ref(), property_account.key().as_ref()], bump)]
+    pub buyer_holder: Account<'info, HolderState>,
+    #[account(init_if_needed, payer = seller, space = HolderState::LEN, seeds = [b"holder", seller.key().as_ref(), property_account.key().as_ref()], bump)]
+    pub seller_holder: Account<'info, HolderState>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order: SellOrder)]
+pub struct ExecuteSignedOrder<'info> {
+    #[account(mut)]
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    /// CHECK: seller wallet; must match the signed order
+    #[account(address = order.seller)]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = buyer_usdc.owner == buyer.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = buyer_usdc.mint == seller_usdc.mint @ ErrorCode::MintMismatch
+    )]
+    pub buyer_usdc: Account<'info, TokenAccount>,
+    #[account(mut, constraint = seller_usdc.owner == seller.key() @ ErrorCode::InvalidTokenOwner)]
+    pub seller_usdc: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = buyer_fraction_ata.owner == buyer.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = buyer_fraction_ata.mint == property_account.fraction_mint @ ErrorCode::MintMismatch
+    )]
+    pub buyer_fraction_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = seller_fraction_ata.owner == seller.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = seller_fraction_ata.mint == property_account.fraction_mint @ ErrorCode::MintMismatch
+    )]
+    pub seller_fraction_ata: Account<'info, TokenAccount>,
+
+    /// CHECK: delegate PDA approved by the seller on their fraction account
+    /// seeds: ["order_delegate", property_account]
+    #[account(seeds = [b"order_delegate", property_account.key().as_ref()], bump)]
+    pub order_delegate: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    #[account(mut, address = protocol_config.treasury, constraint = treasury_usdc.mint == buyer_usdc.mint)]
+    pub treasury_usdc: Account<'info, TokenAccount>,
+    #[account(mut, constraint = sponsor_usdc.owner == property_account.authority @ ErrorCode::Unauthorized, constraint = sponsor_usdc.mint == buyer_usdc.mint)]
+    pub sponsor_usdc: Account<'info, TokenAccount>,
+
+    #[account(init_if_needed, payer = buyer, space = PriceOracle::LEN, seeds = [b"price_oracle", property_account.key().as_ref()], bump)]
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+
+    #[account(init_if_needed, payer = buyer, space = HolderState::LEN, seeds = [b"holder", buyer.key().as_ref(), property_account.key().as_ref()], bump)]
+    pub buyer_holder: Account<'info, HolderState>,
+    #[account(mut, seeds = [b"holder", order.seller.as_ref(), property_account.key().as_ref()], bump)]
+    pub seller_holder: Account<'info, HolderState>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + 32 + 8 + 8 + 1,
+        seeds = [b"order_nonce", order.seller.as_ref(), &order.nonce.to_le_bytes()],
+        bump
+    )]
+    pub order_nonce: Account<'info, OrderNonce>,
+
+    /// CHECK: instructions sysvar, used to inspect the ed25519 verify instruction
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateListing<'info> {
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    #[account(mut, seeds = [b"holder", seller.key().as_ref(), property_account.key().as_ref()], bump)]
+    pub seller_holder: Account<'info, HolderState>,
+    #[account(
+        mut,
+        constraint = seller_fraction_ata.owner == seller.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = seller_fraction_ata.mint == property_account.fraction_mint @ ErrorCode::MintMismatch
+    )]
+    pub seller_fraction_ata: Account<'info, TokenAccount>,
+    #[account(address = property_account.fraction_mint @ ErrorCode::MintMismatch)]
+    pub fraction_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"listing", property_account.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = fraction_mint,
+        associated_token::authority = listing
+    )]
+    pub listing_escrow: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FillListing<'info> {
+    #[account(mut)]
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+
+    #[account(
+        mut,
+        has_one = seller,
+        seeds = [b"listing", property_account.key().as_ref(), seller.key().as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    /// CHECK: seller wallet; receives the listing rent when it closes
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = buyer_usdc.owner == buyer.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = buyer_usdc.mint == seller_usdc.mint @ ErrorCode::MintMismatch
+    )]
+    pub buyer_usdc: Account<'info, TokenAccount>,
+    #[account(mut, constraint = seller_usdc.owner == seller.key() @ ErrorCode::InvalidTokenOwner)]
+    pub seller_usdc: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = buyer_fraction_ata.owner == buyer.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = buyer_fraction_ata.mint == property_account.fraction_mint @ ErrorCode::MintMismatch
+    )]
+    pub buyer_fraction_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = property_account.fraction_mint,
+        associated_token::authority = listing
+    )]
+    pub listing_escrow: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    #[account(mut, address = protocol_config.treasury, constraint = treasury_usdc.mint == buyer_usdc.mint)]
+    pub treasury_usdc: Account<'info, TokenAccount>,
+    #[account(mut, constraint = sponsor_usdc.owner == property_account.authority @ ErrorCode::Unauthorized, constraint = sponsor_usdc.mint == buyer_usdc.mint)]
+    pub sponsor_usdc: Account<'info, TokenAccount>,
+
+    #[account(init_if_needed, payer = buyer, space = PriceOracle::LEN, seeds = [b"price_oracle", property_account.key().as_ref()], bump)]
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+
+    #[account(init_if_needed, payer = buyer, space = HolderState::LEN, seeds = [b"holder", buyer.key().as_ref(), property_account.key().as_ref()], bump)]
+    pub buyer_holder: Account<'info, HolderState>,
+    #[account(mut, seeds = [b"holder", seller.key().as_ref(), property_account.key().as_ref()], bump)]
+    pub seller_holder: Account<'info, HolderState>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    pub property_account: Account<'info, PropertyAccount>,
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    #[account(
+        mut,
+        has_one = seller,
+        close = seller,
+        seeds = [b"listing", property_account.key().as_ref(), seller.key().as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+    #[account(
+        mut,
+        associated_token::mint = property_account.fraction_mint,
+        associated_token::authority = listing
+    )]
+    pub listing_escrow: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"holder", seller.key().as_ref(), property_account.key().as_ref()], bump)]
+    pub seller_holder: Account<'info, HolderState>,
+    #[account(mut, constraint = seller_fraction_ata.owner == seller.key() @ ErrorCode::InvalidTokenOwner)]
+    pub seller_fraction_ata: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetRofrWindow<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    pub property_account: Account<'info, PropertyAccount>,
+    #[account(seeds = [b"price_oracle", property_account.key().as_ref()], bump)]
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+}
+
+#[derive(Accounts)]
+pub struct HolderView<'info> {
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+    /// CHECK: wallet of the holder; only used for PDA derivation
+    pub holder_wallet: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"holder", holder_wallet.key().as_ref(), property_account.key().as_ref()],
+        bump = holder_state.bump
+    )]
+    pub holder_state: Box<Account<'info, HolderState>>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteTrade<'info> {
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct TransferFractions<'info> {
+    #[account(mut)]
+    pub property_account: Account<'info, PropertyAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", source_owner.key().as_ref(), property_account.key().as_ref()],
+        bump
+    )]
+    pub source_holder: Account<'info, HolderState>,
+    #[account(
+        init_if_needed,
+        payer = dest_owner,
+        space = HolderState::LEN,
+        seeds = [b"holder", dest_owner.key().as_ref(), property_account.key().as_ref()],
+        bump
+    )]
+    pub dest_holder: Account<'info, HolderState>,
+
+    #[account(
+        mut,
+        constraint = source_fraction_ata.owner == source_owner.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = source_fraction_ata.mint == property_account.fraction_mint @ ErrorCode::MintMismatch
+    )]
+    pub source_fraction_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = dest_fraction_ata.owner == dest_owner.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = dest_fraction_ata.mint == property_account.fraction_mint @ ErrorCode::MintMismatch
+    )]
+    pub dest_fraction_ata: Account<'info, TokenAccount>,
+
+    pub source_owner: Signer<'info>,
+    #[account(mut)]
+    pub dest_owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositRent<'info> {
+    #[account(mut)]
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"rent_payer", property_account.key().as_ref(), payer.key().as_ref()],
+        bump = rent_payer.bump
+    )]
+    pub rent_payer: Option<Account<'info, RentPayer>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 16 + 32 + 32 + 1,
+        seeds = [b"epoch", property_account.key().as_ref(), &property_account.epoch_count.to_le_bytes()],
+        bump
+    )]
+    pub distribution_epoch: Box<Account<'info, DistributionEpoch>>,
+    #[account(mut, constraint = payer_usdc.mint == property_account.payment_mint @ ErrorCode::MintMismatch)]
+    pub payer_usdc: Account<'info, TokenAccount>,
+    /// CHECK: rent_vault PDA (authority of rent_vault_ata)
+    #[account(seeds = [b"rent_vault", property_account.fraction_mint.as_ref()], bump)]
+    pub rent_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = rent_vault_ata.owner == rent_vault.key() @ ErrorCode::InvalidRentVault,
+        constraint = rent_vault_ata.mint == property_account.payment_mint @ ErrorCode::MintMismatch
+    )]
+    pub rent_vault_ata: Account<'info, TokenAccount>,
+    /// CHECK: reserve_vault PDA (authority of reserve_vault_ata)
+    #[account(seeds = [b"reserve_vault", property_account.key().as_ref()], bump)]
+    pub reserve_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = reserve_vault_ata.owner == reserve_vault.key() @ ErrorCode::InvalidReserveVault,
+        constraint = reserve_vault_ata.mint == rent_vault_ata.mint @ ErrorCode::MintMismatch
+    )]
+    pub reserve_vault_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = sponsor_usdc.owner == property_account.authority @ ErrorCode::InvalidTokenOwner,
+        constraint = sponsor_usdc.mint == rent_vault_ata.mint @ ErrorCode::MintMismatch
+    )]
+    pub sponsor_usdc: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeclareScripDividend<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimScrip<'info> {
+    #[account(mut)]
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+    pub holder: Signer<'info>,
+    #[account(mut, seeds = [b"holder", holder.key().as_ref(), property_account.key().as_ref()], bump)]
+    pub holder_state: Box<Account<'info, HolderState>>,
+    #[account(
+        mut,
+        constraint = holder_fraction_ata.owner == holder.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = holder_fraction_ata.mint == property_account.fraction_mint @ ErrorCode::MintMismatch
+    )]
+    pub holder_fraction_ata: Account<'info, TokenAccount>,
+    #[account(mut, address = property_account.fraction_mint @ ErrorCode::MintMismatch)]
+    pub fraction_mint: Account<'info, Mint>,
+    /// CHECK: mint authority PDA
+    #[account(seeds = [b"fraction_authority", property_account.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateHolderSettings<'info> {
+    pub property_account: Account<'info, PropertyAccount>,
+    pub holder: Signer<'info>,
+    #[account(mut, seeds = [b"holder", holder.key().as_ref(), property_account.key().as_ref()], bump)]
+    pub holder_state: Account<'info, HolderState>,
+}
+
+#[derive(Accounts)]
+pub struct ReinvestRent<'info> {
+    #[account(mut)]
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+    /// CHECK: wallet of the DRIP holder; only used for PDA derivation
+    pub holder_wallet: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"holder", holder_wallet.key().as_ref(), property_account.key().as_ref()],
+        bump
+    )]
+    pub holder_state: Box<Account<'info, HolderState>>,
+    /// CHECK: holder's TaxProfile PDA; may be uninitialized (no withholding)
+    #[account(seeds = [b"tax_profile", holder_wallet.key().as_ref()], bump)]
+    pub tax_profile: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = holder_fraction_ata.owner == holder_wallet.key() @ ErrorCode::InvalidTokenOwner,
+        constraint = holder_fraction_ata.mint == property_account.fraction_mint @ ErrorCode::MintMismatch
+    )]
+    pub holder_fraction_ata: Account<'info, TokenAccount>,
+    #[account(mut, address = property_account.fraction_mint @ ErrorCode::MintMismatch)]
+    pub fraction_mint: Account<'info, Mint>,
+    /// CHECK: mint authority PDA
+    #[account(seeds = [b"fraction_authority", property_account.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: rent_vault PDA (authority of rent_vault_ata)
+    #[account(seeds = [b"rent_vault", property_account.fraction_mint.as_ref()], bump)]
+    pub rent_vault: UncheckedAccount<'info>,
+    #[account(mut, constraint = rent_vault_ata.owner == rent_vault.key() @ ErrorCode::InvalidRentVault)]
+    pub rent_vault_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = sponsor_usdc.owner == property_account.authority @ ErrorCode::InvalidTokenOwner,
+        constraint = sponsor_usdc.mint == rent_vault_ata.mint @ ErrorCode::MintMismatch
+    )]
+    pub sponsor_usdc: Account<'info, TokenAccount>,
+    /// CHECK: holder's TaxYearRecord PDA for the current year; checked and created by `record_tax_year`
+    #[account(mut)]
+    pub tax_year_record: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
 
+#[derive(Accounts)]
+pub struct DistributeRentBatch<'info> {
+    #[account(mut)]
+    pub property_account: Account<'info, PropertyAccount>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(mut, constraint = cranker_usdc.mint == rent_vault_ata.mint @ ErrorCode::MintMismatch)]
+    pub cranker_usdc: Account<'info, TokenAccount>,
+    /// CHECK: rent_vault PDA (authority of rent_vault_ata)
+    #[account(seeds = [b"rent_vault", property_account.fraction_mint.as_ref()], bump)]
+    pub rent_vault: UncheckedAccount<'info>,
+    #[account(mut, constraint = rent_vault_ata.owner == rent_vault.key() @ ErrorCode::InvalidRentVault)]
+    pub rent_vault_ata: Account<'info, TokenAccount>,
+    /// Tax vault ATA; required when a holder in the batch is subject to withholding
+    #[account(mut)]
+    pub tax_vault_ata: Option<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRentMulti<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(mut)]
+    pub receiver_usdc: Account<'info, TokenAccount>,
+    /// CHECK: holder's TaxProfile PDA; may be uninitialized (no withholding)
+    #[account(seeds = [b"tax_profile", holder.key().as_ref()], bump)]
+    pub tax_profile: UncheckedAccount<'info>,
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepRentDust<'info> {
+    #[account(has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+    /// CHECK: rent_vault PDA (authority of rent_vault_ata)
+    #[account(seeds = [b"rent_vault", property_account.fraction_mint.as_ref()], bump)]
+    pub rent_vault: UncheckedAccount<'info>,
+    #[account(mut, constraint = rent_vault_ata.owner == rent_vault.key() @ ErrorCode::InvalidRentVault)]
+    pub rent_vault_ata: Account<'info, TokenAccount>,
+    #[account(mut, constraint = destination_usdc.mint == rent_vault_ata.mint @ ErrorCode::MintMismatch)]
+    pub destination_usdc: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct EscheatUnclaimed<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Box<Account<'info, PropertyAccount>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: wallet of the dormant holder; only used for PDA derivation
+    pub holder_wallet: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"holder", holder_wallet.key().as_ref(), property_account.key().as_ref()],
+        bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"escheat", property_account.key().as_ref(), holder_wallet.key().as_ref()],
+        bump
+    )]
+    pub escheat_record: Account<'info, EscheatRecord>,
+    /// CHECK: rent_vault PDA (authority of rent_vault_ata)
+    #[account(seeds = [b"rent_vault", property_account.fraction_mint.as_ref()], bump)]
+    pub rent_vault: UncheckedAccount<'info>,
+    #[account(mut, constraint = rent_vault_ata.owner == rent_vault.key() @ ErrorCode::InvalidRentVault)]
+    pub rent_vault_ata: Account<'info, TokenAccount>,
+    /// CHECK: escheat_vault PDA (authority of escheat_vault_ata)
+    #[account(seeds = [b"escheat_vault", property_account.key().as_ref()], bump)]
+    pub escheat_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = escheat_vault_ata.owner == escheat_vault.key() @ ErrorCode::InvalidEscheatVault,
+        constraint = escheat_vault_ata.mint == rent_vault_ata.mint @ ErrorCode::MintMismatch
+    )]
+    pub escheat_vault_ata: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimEscheated<'info> {
+    pub property_account: Account<'info, PropertyAccount>,
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"holder", holder.key().as_ref(), property_account.key().as_ref()],
+        bump = holder_state.bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    #[account(
+        mut,
+        seeds = [b"escheat", property_account.key().as_ref(), holder.key().as_ref()],
+        bump = escheat_record.bump
+    )]
+    pub escheat_record: Account<'info, EscheatRecord>,
+    /// CHECK: escheat_vault PDA (authority of escheat_vault_ata)
+    #[account(seeds = [b"escheat_vault", property_account.key().as_ref()], bump)]
+    pub escheat_vault: UncheckedAccount<'info>,
+    #[account(mut, constraint = escheat_vault_ata.owner == escheat_vault.key() @ ErrorCode::InvalidEscheatVault)]
+    pub escheat_vault_ata: Account<'info, TokenAccount>,
+    #[account(mut, constraint = receiver_usdc.mint == escheat_vault_ata.mint @ ErrorCode::MintMismatch)]
+    pub receiver_usdc: Account<'info, TokenAccount>,
+    /// CHECK: holder's TaxProfile PDA; may be uninitialized (no withholding)
+    #[account(seeds = [b"tax_profile", holder.key().as_ref()], bump)]
+    pub tax_profile: UncheckedAccount<'info>,
+    /// Tax vault ATA; required only when tax is withheld
+    #[account(mut)]
+    pub tax_vault_ata: Option<Account<'info, TokenAccount>>,
+    /// CHECK: holder's TaxYearRecord PDA for the current year; checked and created by `record_tax_year`
+    #[account(mut)]
+    pub tax_year_record: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRent<'info> {
+    #[account(mut)]
+    pub property_account: Account<'info, PropertyAccount>,
+    
+    /// CHECK: wallet of the holder being paid; only used for PDA derivation
+    pub holder_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"holder", holder_wallet.key().as_ref(), property_account.key().as_ref()],
+        bump = holder_state.bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    
+    /// Holder or their claim delegate
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(mut)]
+    pub rent_vault_ata: Account<'info, TokenAccount>,
+    
+    /// CHECK: rent_vault PDA (authority of rent_vault_ata)
+    pub rent_vault: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub receiver_usdc: Account<'info, TokenAccount>,
+
+    /// CHECK: holder's TaxProfile PDA; may be uninitialized (no withholding)
+    #[account(seeds = [b"tax_profile", holder_wallet.key().as_ref()], bump)]
+    pub tax_profile: UncheckedAccount<'info>,
+
+    /// Tax vault ATA; required only when tax is withheld
+    #[account(mut)]
+    pub tax_vault_ata: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: holder's TaxYearRecord PDA for the current year; checked and created by `record_tax_year`
+    #[account(mut)]
+    pub tax_year_record: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPropertyManager<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordExpense<'info> {
+    #[account(mut, has_one = manager @ ErrorCode::Unauthorized)]
+    pub property_account: Account<'info, PropertyAccount>,
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    #[account(
+        init,
+        payer = manager,
+        space = 8 + 32 + 8 + 1 + 8 + 32 + 32 + 8 + 8 + 1 + 1,
+        seeds = [b"expense", property_account.key().as_ref(), &property_account.expense_count.to_le_bytes()],
+        bump
+    )]
+    pub expense: Account<'info, ExpenseRecord>,
+    #[account(constraint = payee_usdc.mint == property_account.payment_mint @ ErrorCode::MintMismatch)]
+    pub payee_usdc: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PayExpense<'info> {
+    #[account(mut, has_one = manager @ ErrorCode::Unauthorized)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub manager: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"expense", property_account.key().as_ref(), &expense.id.to_le_bytes()],
+        bump = expense.bump
+    )]
+    pub expense: Account<'info, ExpenseRecord>,
+    #[account(mut, address = expense.payee, constraint = payee_usdc.mint == rent_vault_ata.mint @ ErrorCode::MintMismatch)]
+    pub payee_usdc: Account<'info, TokenAccount>,
+    /// CHECK: rent_vault PDA (authority of rent_vault_ata)
+    #[account(seeds = [b"rent_vault", property_account.fraction_mint.as_ref()], bump)]
+    pub rent_vault: UncheckedAccount<'info>,
+    #[account(mut, constraint = rent_vault_ata.owner == rent_vault.key() @ ErrorCode::InvalidRentVault)]
+    pub rent_vault_ata: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelExpense<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == property_account.manager || signer.key() == property_account.authority @ ErrorCode::Unauthorized
+    )]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"expense", property_account.key().as_ref(), &expense.id.to_le_bytes()],
+        bump = expense.bump
+    )]
+    pub expense: Account<'info, ExpenseRecord>,
+}
+
+#[derive(Accounts)]
+pub struct SetRentFees<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOfferingPrice<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(payer: Pubkey)]
+pub struct AddRentPayer<'info> {
+    #[account(has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 32 + 1,
+        seeds = [b"rent_payer", property_account.key().as_ref(), payer.as_ref()],
+        bump
+    )]
+    pub rent_payer: Account<'info, RentPayer>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveRentPayer<'info> {
+    #[account(has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"rent_payer", property_account.key().as_ref(), rent_payer.payer.as_ref()],
+        bump = rent_payer.bump
+    )]
+    pub rent_payer: Account<'info, RentPayer>,
+}
+
+#[derive(Accounts)]
+pub struct SetHoldingPeriod<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEscheatPolicy<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct DepositNftIntoVault<'info> {
-    #[account(mut, has_one = nft_mint)]
+pub struct SetCrankReward<'info> {
+    #[account(mut, has_one = authority)]
     pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+}
 
-    /// CHECK: NFT mint
-    pub nft_mint: AccountInfo<'info>,
-
-    /// Token account holding the NFT (owned by nft_vault PDA)
+#[derive(Accounts)]
+pub struct FundCrankRewards<'info> {
     #[account(mut)]
-    pub nft_vault_ata: Account<'info, TokenAccount>,
+    pub property_account: Account<'info, PropertyAccount>,
+    pub funder: Signer<'info>,
+    #[account(mut)]
+    pub funder_usdc: Account<'info, TokenAccount>,
+    /// CHECK: rent_vault PDA (authority of rent_vault_ata)
+    #[account(seeds = [b"rent_vault", property_account.fraction_mint.as_ref()], bump)]
+    pub rent_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = rent_vault_ata.owner == rent_vault.key() @ ErrorCode::InvalidRentVault,
+        constraint = rent_vault_ata.mint == property_account.payment_mint @ ErrorCode::MintMismatch
+    )]
+    pub rent_vault_ata: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct MintFractions<'info> {
+pub struct WithdrawCrankFunds<'info> {
     #[account(mut, has_one = authority)]
     pub property_account: Account<'info, PropertyAccount>,
-    #[account(mut)]
-    pub fraction_mint: Account<'info, Mint>,
-    /// CHECK: mint authority PDA
-    pub mint_authority: UncheckedAccount<'info>,
-    #[account(mut)]
-    pub destination: Account<'info, TokenAccount>,
     pub authority: Signer<'info>,
+    #[account(mut, constraint = destination_usdc.mint == rent_vault_ata.mint @ ErrorCode::MintMismatch)]
+    pub destination_usdc: Account<'info, TokenAccount>,
+    /// CHECK: rent_vault PDA (authority of rent_vault_ata)
+    #[account(seeds = [b"rent_vault", property_account.fraction_mint.as_ref()], bump)]
+    pub rent_vault: UncheckedAccount<'info>,
+    #[account(mut, constraint = rent_vault_ata.owner == rent_vault.key() @ ErrorCode::InvalidRentVault)]
+    pub rent_vault_ata: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct BuyFractions<'info> {
-    #[account(mut)]
+pub struct SetReservePolicy<'info> {
+    #[account(mut, has_one = authority)]
     pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+}
 
+#[derive(Accounts)]
+pub struct RequestReserveDrawdown<'info> {
+    #[account(mut, has_one = manager @ ErrorCode::Unauthorized)]
+    pub property_account: Account<'info, PropertyAccount>,
     #[account(mut)]
-    pub buyer: Signer<'info>,
-    #[account(mut)]
-    pub seller: Signer<'info>,
-
-    #[account(mut)]
-    pub buyer_usdc: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub seller_usdc: Account<'info, TokenAccount>,
-
-    #[account(mut)]
-    pub buyer_fraction_ata: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub seller_fraction_ata: Account<'info, TokenAccount>,
-
-    #[account(init_if_needed, payer = buyer, space = 8 + 32 + 32 + 8 + 16 + 16 + 1, seeds = [b"holder", buyer.key().as_ref(), property_account.key().as_ref()], bump)]
-    pub buyer_holder: Account<'info, HolderState>,
-    #[account(init_if_needed, payer = seller, space = 8 + 32 + 32 + 8 + 16 + 16 + 1, seeds = [b"holder", seller.key().as_ref(), property_account.key().as_ref()], bump)]
-    pub seller_holder: Account<'info, HolderState>,
-
-    pub token_program: Program<'info, Token>,
+    pub manager: Signer<'info>,
+    #[account(
+        init,
+        payer = manager,
+        space = 8 + 32 + 8 + 8 + 32 + 32 + 8 + 8 + 1 + 1,
+        seeds = [b"drawdown", property_account.key().as_ref(), &property_account.drawdown_count.to_le_bytes()],
+        bump
+    )]
+    pub drawdown: Account<'info, ReserveDrawdown>,
+    pub recipient_usdc: Account<'info, TokenAccount>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct TransferFractions<'info> {
-    #[account(mut)]
+pub struct ApproveReserveDrawdown<'info> {
+    #[account(mut, has_one = authority)]
     pub property_account: Account<'info, PropertyAccount>,
-
+    pub authority: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"holder", source_owner.key().as_ref(), property_account.key().as_ref()],
-        bump
-    )]
-    pub source_holder: Account<'info, HolderState>,
-    #[account(
-        init_if_needed,
-        payer = dest_owner,
-        space = 8 + 32 + 32 + 8 + 16 + 16 + 1,
-        seeds = [b"holder", dest_owner.key().as_ref(), property_account.key().as_ref()],
-        bump
+        seeds = [b"drawdown", property_account.key().as_ref(), &drawdown.id.to_le_bytes()],
+        bump = drawdown.bump
     )]
-    pub dest_holder: Account<'info, HolderState>,
-
-    #[account(mut)]
-    pub source_fraction_ata: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub dest_fraction_ata: Account<'info, TokenAccount>,
-
-    pub source_owner: Signer<'info>,
-    #[account(mut)]
-    pub dest_owner: Signer<'info>,
-
+    pub drawdown: Account<'info, ReserveDrawdown>,
+    #[account(mut, address = drawdown.recipient, constraint = recipient_usdc.mint == reserve_vault_ata.mint @ ErrorCode::MintMismatch)]
+    pub recipient_usdc: Account<'info, TokenAccount>,
+    /// CHECK: reserve_vault PDA (authority of reserve_vault_ata)
+    #[account(seeds = [b"reserve_vault", property_account.key().as_ref()], bump)]
+    pub reserve_vault: UncheckedAccount<'info>,
+    #[account(mut, constraint = reserve_vault_ata.owner == reserve_vault.key() @ ErrorCode::InvalidReserveVault)]
+    pub reserve_vault_ata: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct DepositRent<'info> {
+pub struct ReleaseExcessReserve<'info> {
     #[account(mut)]
     pub property_account: Account<'info, PropertyAccount>,
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(mut)]
-    pub payer_usdc: Account<'info, TokenAccount>,
-    #[account(mut)]
+    /// CHECK: reserve_vault PDA (authority of reserve_vault_ata)
+    #[account(seeds = [b"reserve_vault", property_account.key().as_ref()], bump)]
+    pub reserve_vault: UncheckedAccount<'info>,
+    #[account(mut, constraint = reserve_vault_ata.owner == reserve_vault.key() @ ErrorCode::InvalidReserveVault)]
+    pub reserve_vault_ata: Account<'info, TokenAccount>,
+    /// CHECK: rent_vault PDA (authority of rent_vault_ata)
+    #[account(seeds = [b"rent_vault", property_account.fraction_mint.as_ref()], bump)]
+    pub rent_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = rent_vault_ata.owner == rent_vault.key() @ ErrorCode::InvalidRentVault,
+        constraint = rent_vault_ata.mint == reserve_vault_ata.mint @ ErrorCode::MintMismatch
+    )]
     pub rent_vault_ata: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimRent<'info> {
+pub struct AddRewardMint<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+    pub reward_mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct DepositReward<'info> {
     #[account(mut)]
     pub property_account: Account<'info, PropertyAccount>,
-    
+    pub payer: Signer<'info>,
+    pub reward_mint: Account<'info, Mint>,
+    #[account(mut, constraint = payer_token.mint == reward_mint.key() @ ErrorCode::MintMismatch)]
+    pub payer_token: Account<'info, TokenAccount>,
+    /// CHECK: reward vault PDA (authority of reward_vault_ata)
+    #[account(seeds = [b"reward_vault", property_account.key().as_ref(), reward_mint.key().as_ref()], bump)]
+    pub reward_vault: UncheckedAccount<'info>,
     #[account(
-        init_if_needed,
-        payer = payer,
-        space = 8 + 32 + 32 + 8 + 16 + 16 + 1,
-        seeds = [b"holder", payer.key().as_ref(), property_account.key().as_ref()],
-        bump
+        mut,
+        constraint = reward_vault_ata.owner == reward_vault.key() @ ErrorCode::InvalidRewardVault,
+        constraint = reward_vault_ata.mint == reward_mint.key() @ ErrorCode::MintMismatch
     )]
-    pub holder_state: Account<'info, HolderState>,
-    
+    pub reward_vault_ata: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
     #[account(mut)]
-    pub payer: Signer<'info>,
-    
-    /// Holder's fraction token account - needed to get balance on first claim
-    pub holder_fraction_ata: Account<'info, TokenAccount>,
-    
+    pub property_account: Account<'info, PropertyAccount>,
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(mut, seeds = [b"holder", holder.key().as_ref(), property_account.key().as_ref()], bump)]
+    pub holder_state: Account<'info, HolderState>,
+
     #[account(mut)]
     pub rent_vault_ata: Account<'info, TokenAccount>,
-    
     /// CHECK: rent_vault PDA (authority of rent_vault_ata)
     pub rent_vault: UncheckedAccount<'info>,
-    
     #[account(mut)]
     pub receiver_usdc: Account<'info, TokenAccount>,
-    
+    /// CHECK: holder's TaxProfile PDA; may be uninitialized (no withholding)
+    #[account(seeds = [b"tax_profile", holder.key().as_ref()], bump)]
+    pub tax_profile: UncheckedAccount<'info>,
+    /// Tax vault ATA; required only when tax is withheld
+    #[account(mut)]
+    pub tax_vault_ata: Option<Account<'info, TokenAccount>>,
+    /// CHECK: holder's TaxYearRecord PDA for the current year; checked and created by `record_tax_year`
+    #[account(mut)]
+    pub tax_year_record: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -533,13 +4095,31 @@ pub struct BurnFractions<'info> {
     pub holder: Signer<'info>,
     #[account(mut, seeds = [b"holder", holder.key().as_ref(), property_account.key().as_ref()], bump)]
     pub holder_state: Account<'info, HolderState>,
-    #[account(mut)]
+    #[account(mut, address = property_account.fraction_mint @ ErrorCode::MintMismatch)]
     pub fraction_mint: Account<'info, Mint>,
     #[account(mut)]
     pub holder_fraction_ata: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct ReconcileSupply<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+    #[account(address = property_account.fraction_mint @ ErrorCode::MintMismatch)]
+    pub fraction_mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct ResyncSupply<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+    #[account(address = property_account.fraction_mint @ ErrorCode::MintMismatch)]
+    pub fraction_mint: Account<'info, Mint>,
+}
+
 #[derive(Accounts)]
 pub struct UnlockNft<'info> {
     #[account(mut)]
@@ -559,6 +4139,239 @@ pub struct UnlockNft<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 32 + 2 + 8 + 4 + 32 * MAX_FEE_EXEMPT + 1 + 32,
+        seeds = [b"protocol_config"],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub treasury_usdc: Account<'info, TokenAccount>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Rwa>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(mut, seeds = [b"protocol_config"], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub admin: Signer<'info>,
+    pub treasury_usdc: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetComplianceAuthority<'info> {
+    #[account(mut, seeds = [b"protocol_config"], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(holder: Pubkey)]
+pub struct SetTaxProfile<'info> {
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump, has_one = compliance_authority @ ErrorCode::Unauthorized)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub compliance_authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = compliance_authority,
+        space = 8 + 32 + 2 + 2 + 8 + 1,
+        seeds = [b"tax_profile", holder.as_ref()],
+        bump
+    )]
+    pub tax_profile: Account<'info, TaxProfile>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemitWithholding<'info> {
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump, has_one = compliance_authority @ ErrorCode::Unauthorized)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub compliance_authority: Signer<'info>,
+    pub property_account: Account<'info, PropertyAccount>,
+    /// CHECK: tax_vault PDA (authority of tax_vault_ata)
+    #[account(seeds = [b"tax_vault", property_account.key().as_ref()], bump)]
+    pub tax_vault: UncheckedAccount<'info>,
+    #[account(mut, constraint = tax_vault_ata.owner == tax_vault.key() @ ErrorCode::InvalidTaxVault)]
+    pub tax_vault_ata: Account<'info, TokenAccount>,
+    #[account(mut, constraint = destination.mint == tax_vault_ata.mint @ ErrorCode::MintMismatch)]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeExemption<'info> {
+    #[account(mut, seeds = [b"protocol_config"], bump = protocol_config.bump, has_one = admin)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyPendingEpoch<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+    /// The latest epoch, which is the pending one
+    #[account(
+        mut,
+        seeds = [b"epoch", property_account.key().as_ref(), &property_account.epoch_count.saturating_sub(1).to_le_bytes()],
+        bump = distribution_epoch.bump
+    )]
+    pub distribution_epoch: Box<Account<'info, DistributionEpoch>>,
+}
+
+#[derive(Accounts)]
+pub struct SetRentStreamPeriod<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSponsorFee<'info> {
+    #[account(mut, has_one = authority)]
+    pub property_account: Account<'info, PropertyAccount>,
+    pub authority: Signer<'info>,
+}
+
+// --------------------
+// Events
+// --------------------
+
+#[event]
+pub struct RentDeposited {
+    pub property: Pubkey,
+    pub payer: Pubkey,
+    pub reference: [u8; 32],
+    pub gross_amount: u64,
+    pub management_fee: u64,
+    pub performance_fee: u64,
+    pub expenses_withheld: u64,
+    pub reserve_withheld: u64,
+    pub net_amount: u64,
+}
+
+#[event]
+pub struct RentWithheld {
+    pub property: Pubkey,
+    pub holder: Pubkey,
+    pub tax_year: u16,
+    pub gross_amount: u64,
+    pub withheld: u64,
+}
+
+#[event]
+pub struct ExpenseRecorded {
+    pub property: Pubkey,
+    pub id: u64,
+    pub category: ExpenseCategory,
+    pub amount: u64,
+    pub invoice_hash: [u8; 32],
+}
+
+#[event]
+pub struct ExpensePaid {
+    pub property: Pubkey,
+    pub id: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ExpenseCancelled {
+    pub property: Pubkey,
+    pub id: u64,
+    pub amount: u64,
+    pub released: u64,            // Withheld rent credited back to holders
+}
+
+#[event]
+pub struct SpecialDistribution {
+    pub property: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RentSettled {
+    pub property: Pubkey,
+    pub holder: Pubkey,
+    pub from_epoch: u64,
+    pub to_epoch: u64,
+    pub balance: u64,
+    pub amount_scaled: u128,
+    pub forfeited_scaled: u128,
+}
+
+#[event]
+pub struct RentEscheated {
+    pub property: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EscheatReclaimed {
+    pub property: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SupplyReconciled {
+    pub property: Pubkey,
+    pub minted_fractions: u64,
+    pub mint_supply: u64,
+    pub halted: bool,
+}
+
+#[event]
+pub struct RentReinvested {
+    pub property: Pubkey,
+    pub holder: Pubkey,
+    pub fraction_amount: u64,
+    pub price_per_fraction: u64,
+    pub cost: u64,
+}
+
+#[event]
+pub struct ScripDeclared {
+    pub property: Pubkey,
+    pub fraction_amount: u64,
+}
+
+#[event]
+pub struct ScripClaimed {
+    pub property: Pubkey,
+    pub holder: Pubkey,
+    pub fraction_amount: u64,
+}
+
+#[event]
+pub struct RentDustSwept {
+    pub property: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FractionsTraded {
+    pub property: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub fraction_amount: u64,
+    pub price_per_fraction: u64,
+    pub price: u64,
+    pub protocol_fee: u64,
+    pub sponsor_fee: u64,
+}
+
 // --------------------
 // Errors
 // --------------------
@@ -581,4 +4394,106 @@ pub enum ErrorCode {
     FractionsNotBurned,
     #[msg("Metadata URI exceeds 200 bytes")]
     MetadataUriTooLong,
+    #[msg("Fee exceeds the allowed maximum")]
+    FeeTooHigh,
+    #[msg("Fee-exempt list is full")]
+    FeeExemptListFull,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Order or quote has expired")]
+    OrderExpired,
+    #[msg("Total cost exceeds the maximum allowed")]
+    SlippageExceeded,
+    #[msg("Token account is not owned by the expected wallet")]
+    InvalidTokenOwner,
+    #[msg("Token account mint does not match")]
+    MintMismatch,
+    #[msg("Order does not match this property")]
+    InvalidOrder,
+    #[msg("Missing ed25519 signature verification instruction")]
+    MissingSignatureVerification,
+    #[msg("Ed25519 instruction does not verify this order")]
+    InvalidSignatureVerification,
+    #[msg("Listing is in its right-of-first-refusal window; only existing holders may buy")]
+    RofrWindowActive,
+    #[msg("Right of first refusal is enabled; sales to new holders must go through a listing")]
+    RofrRequiresListing,
+    #[msg("Not enough trade history to cover the requested window")]
+    InsufficientPriceHistory,
+    #[msg("Rent vault token account is not owned by the rent vault PDA")]
+    InvalidRentVault,
+    #[msg("Nothing to sweep")]
+    NothingToSweep,
+    #[msg("Reward registry is full")]
+    RewardRegistryFull,
+    #[msg("Reward mint is already registered")]
+    RewardAlreadyRegistered,
+    #[msg("Reward mint is not registered for this property")]
+    RewardNotRegistered,
+    #[msg("Reward vault account does not match the reward vault PDA")]
+    InvalidRewardVault,
+    #[msg("Unexpected remaining accounts")]
+    InvalidRemainingAccounts,
+    #[msg("Expense has already been paid")]
+    ExpenseAlreadyPaid,
+    #[msg("Not enough rent withheld to pay this expense")]
+    InsufficientExpenseFunds,
+    #[msg("Reserve vault token account is not owned by the reserve vault PDA")]
+    InvalidReserveVault,
+    #[msg("Reserve drawdown has already been executed")]
+    DrawdownAlreadyExecuted,
+    #[msg("Reserve balance is insufficient")]
+    InsufficientReserve,
+    #[msg("Holder state does not match the expected PDA")]
+    InvalidHolderState,
+    #[msg("Withholding rate exceeds maximum")]
+    WithholdingTooHigh,
+    #[msg("Tax year record does not match the current year")]
+    InvalidTaxYear,
+    #[msg("Tax profile does not match the expected PDA")]
+    InvalidTaxProfile,
+    #[msg("Invalid tax vault account")]
+    InvalidTaxVault,
+    #[msg("Holders subject to withholding must use claim_rent")]
+    WithholdingRequiresClaimRent,
+    #[msg("A distribution epoch is awaiting its record date")]
+    EpochPending,
+    #[msg("Escheatment policy is not configured")]
+    EscheatmentDisabled,
+    #[msg("Holder has not been inactive long enough")]
+    HolderNotDormant,
+    #[msg("Invalid escheat vault account")]
+    InvalidEscheatVault,
+    #[msg("Nothing to reclaim")]
+    NothingToReclaim,
+    #[msg("minted_fractions does not match the fraction mint supply; run reconcile_supply")]
+    SupplyMismatch,
+    #[msg("Payer is not authorized to deposit rent for this property")]
+    UnauthorizedPayer,
+    #[msg("Holder has not enabled dividend reinvestment")]
+    DripNotEnabled,
+    #[msg("No primary offering is open for this property")]
+    NoOffering,
+    #[msg("Minted and reserved fractions would exceed total_fractions")]
+    ExceedsTotalFractions,
+    #[msg("Balance increase exceeds untracked supply; sync the sending holder first")]
+    UntrackedSupplyExceeded,
+    #[msg("Holder still has a balance or claimable amounts")]
+    HolderNotEmpty,
+    #[msg("Receiver is not the holder's payout destination")]
+    InvalidPayoutDestination,
+    #[msg("Buyer and seller must be different wallets")]
+    SelfTrade,
+    #[msg("Record date is too far in the future")]
+    RecordDateTooFar,
+    #[msg("No distribution epoch is awaiting its record date")]
+    NoPendingEpoch,
+    #[msg("Offering price is above the holder's DRIP maximum")]
+    DripPriceTooHigh,
+    #[msg("Holder reinvests rent; disable DRIP to claim cash")]
+    DripEnabled,
+    #[msg("Expense has been cancelled")]
+    ExpenseCancelled,
+    #[msg("Only the authority or manager may set a future record date")]
+    RecordDateRestricted,
 }
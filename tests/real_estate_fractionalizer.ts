@@ -1,5 +1,5 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { Program, web3, BN } from "@coral-xyz/anchor";
 import { Rwa } from "../target/types/rwa";
 import {
   createMint,
@@ -8,8 +8,30 @@ import {
   getAccount,
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  approve,
+  getAssociatedTokenAddressSync,
+  transfer as splTransfer,
 } from "@solana/spl-token";
 import { assert } from "chai";
+import {
+  payer,
+  sleep,
+  u64,
+  pda,
+  holderPda,
+  fundedWallet,
+  tokenAccount,
+  tokenBalance,
+  expectError,
+  createProperty,
+  mintFractions,
+  depositRent,
+  claimRent,
+  transferFractions,
+  PropertyFixture,
+  ensureProtocolConfig,
+  ProtocolFixture,
+} from "./utils";
 
 describe("rwa - Real Estate Fractionalizer E2E tests", () => {
   const provider = anchor.AnchorProvider.local();
@@ -220,8 +242,13 @@ describe("rwa - Real Estate Fractionalizer E2E tests", () => {
         fractionMint: fractionMint.publicKey,
         mintAuthority,
         destination: authorityFractionAta.address,
+        destinationHolder: anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("holder"), authority.toBuffer(), propertyAccount.publicKey.toBuffer()],
+          program.programId
+        )[0],
         authority,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       })
       .rpc();
@@ -250,13 +277,36 @@ describe("rwa - Real Estate Fractionalizer E2E tests", () => {
 
     const rentAmount = new anchor.BN(10_000_000); // 10 USDC
 
+    const [reserveVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reserve_vault"), propertyAccount.publicKey.toBuffer()],
+      program.programId
+    );
+    const reserveVaultAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      usdcMint,
+      reserveVault,
+      true
+    );
+
+    const [distributionEpoch] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("epoch"), propertyAccount.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
     await program.methods
-      .depositRent(rentAmount)
+      .depositRent(rentAmount, new anchor.BN(0), Array.from(Buffer.alloc(32)))
       .accounts({
         propertyAccount: propertyAccount.publicKey,
         payer: authority,
+        rentPayer: null,
+        distributionEpoch,
         payerUsdc: authorityUsdcAta.address,
+        rentVault,
         rentVaultAta,
+        reserveVault,
+        reserveVaultAta: reserveVaultAta.address,
+        sponsorUsdc: authorityUsdcAta.address,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc();
@@ -301,8 +351,13 @@ describe("rwa - Real Estate Fractionalizer E2E tests", () => {
         fractionMint: fractionMint.publicKey,
         mintAuthority,
         destination: sellerFractionAta.address,
+        destinationHolder: anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("holder"), seller.publicKey.toBuffer(), propertyAccount.publicKey.toBuffer()],
+          program.programId
+        )[0],
         authority,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       })
       .rpc();
@@ -335,11 +390,75 @@ describe("rwa - Real Estate Fractionalizer E2E tests", () => {
       program.programId
     );
 
+    // Protocol fees go to a treasury USDC account; sponsor royalties to the authority
+    const authorityUsdcAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      usdcMint,
+      authority
+    );
+    const treasuryUsdcAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      usdcMint,
+      anchor.web3.Keypair.generate().publicKey
+    );
+    const [protocolConfig] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      program.programId
+    );
+    const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    // Only the upgrade authority may create the singleton config
+    const outsider = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(outsider.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+    );
+    try {
+      await program.methods
+        .initializeProtocolConfig(50, new anchor.BN(0))
+        .accounts({
+          protocolConfig,
+          admin: outsider.publicKey,
+          treasuryUsdc: treasuryUsdcAta.address,
+          program: program.programId,
+          programData,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+      assert.fail("non-upgrade-authority initialized the protocol config");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    await program.methods
+      .initializeProtocolConfig(50, new anchor.BN(0)) // 0.5%, uncapped
+      .accounts({
+        protocolConfig,
+        admin: authority,
+        treasuryUsdc: treasuryUsdcAta.address,
+        program: program.programId,
+        programData,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const [priceOracle] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_oracle"), propertyAccount.publicKey.toBuffer()],
+      program.programId
+    );
+
     const fractionAmount = new anchor.BN(50_000);
-    const price = new anchor.BN(5_000_000); // 5 USDC
+    const pricePerFraction = new anchor.BN(100_000_000); // 100 USDC per whole fraction
+    const maxTotalCost = new anchor.BN(5_000_000); // 5 USDC
+    const validUntil = new anchor.BN(Math.floor(Date.now() / 1000) + 60);
 
     await program.methods
-      .buyFractions(fractionAmount, price)
+      .buyFractions(fractionAmount, pricePerFraction, maxTotalCost, validUntil)
       .accounts({
         propertyAccount: propertyAccount.publicKey,
         buyer: buyer.publicKey,
@@ -348,6 +467,10 @@ describe("rwa - Real Estate Fractionalizer E2E tests", () => {
         sellerUsdc: sellerUsdcAta,
         buyerFractionAta: buyerFractionAta.address,
         sellerFractionAta: sellerFractionAta.address,
+        protocolConfig,
+        treasuryUsdc: treasuryUsdcAta.address,
+        sponsorUsdc: authorityUsdcAta.address,
+        priceOracle,
         buyerHolder,
         sellerHolder,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -361,6 +484,10 @@ describe("rwa - Real Estate Fractionalizer E2E tests", () => {
 
     const buyerHolderAccount = await program.account.holderState.fetch(buyerHolder);
     assert.equal(buyerHolderAccount.balance.toNumber(), 50_000);
+
+    const oracle = await program.account.priceOracle.fetch(priceOracle);
+    assert.equal(oracle.lastPrice.toString(), pricePerFraction.toString());
+    assert.equal(oracle.totalVolume.toNumber(), 50_000);
   });
 
   it("transfers fractions between holders", async () => {
@@ -443,4 +570,650 @@ describe("rwa - Real Estate Fractionalizer E2E tests", () => {
     // Previous balance was 50_000, transferred 10_000, so 40_000, now burned 5_000 = 35_000
     assert.equal(buyerAta.amount.toString(), "35000");
   });
+
+  it("enforces the buyer's limits and settles the exact price", async () => {
+    const ata = (mint: anchor.web3.PublicKey, owner: anchor.web3.PublicKey) =>
+      anchor.utils.token.associatedAddress({ mint, owner });
+    const holder = (owner: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("holder"), owner.toBuffer(), propertyAccount.publicKey.toBuffer()],
+        program.programId
+      )[0];
+    const [protocolConfig] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      program.programId
+    );
+    const [priceOracle] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_oracle"), propertyAccount.publicKey.toBuffer()],
+      program.programId
+    );
+    const config = await program.account.protocolConfig.fetch(protocolConfig);
+    const sponsorUsdc = await ata(usdcMint, authority);
+    const sellerUsdcAta = await ata(usdcMint, seller.publicKey);
+
+    // A fraction account of an unrelated mint
+    const otherMint = await createMint(provider.connection, provider.wallet.payer, authority, null, 6);
+    const buyerOtherAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      otherMint,
+      buyer.publicKey
+    );
+
+    const defaults = {
+      buyerUsdc: await ata(usdcMint, buyer.publicKey),
+      buyerFractionAta: await ata(fractionMint.publicKey, buyer.publicKey),
+    };
+    const fractionAmount = new anchor.BN(10_000);
+    const pricePerFraction = new anchor.BN(100_000_000); // 100 USDC per whole fraction
+    const price = 1_000_000; // 10_000 * 100_000_000 / 10^6
+
+    const buy = (opts: {
+      maxTotalCost?: number;
+      validUntil?: number;
+      buyerUsdc?: anchor.web3.PublicKey;
+      buyerFractionAta?: anchor.web3.PublicKey;
+    }) =>
+      program.methods
+        .buyFractions(
+          fractionAmount,
+          pricePerFraction,
+          new anchor.BN(opts.maxTotalCost ?? price),
+          new anchor.BN(opts.validUntil ?? Math.floor(Date.now() / 1000) + 60)
+        )
+        .accounts({
+          propertyAccount: propertyAccount.publicKey,
+          buyer: buyer.publicKey,
+          seller: seller.publicKey,
+          buyerUsdc: opts.buyerUsdc ?? defaults.buyerUsdc,
+          sellerUsdc: sellerUsdcAta,
+          buyerFractionAta: opts.buyerFractionAta ?? defaults.buyerFractionAta,
+          sellerFractionAta: await ata(fractionMint.publicKey, seller.publicKey),
+          protocolConfig,
+          treasuryUsdc: config.treasury,
+          sponsorUsdc,
+          priceOracle,
+          buyerHolder: holder(buyer.publicKey),
+          sellerHolder: holder(seller.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([buyer, seller])
+        .rpc();
+
+    const rejects = async (tx: Promise<string>, code: string) => {
+      try {
+        await tx;
+        assert.fail(`expected ${code}`);
+      } catch (err) {
+        assert.include(err.toString(), code);
+      }
+    };
+    await rejects(buy({ maxTotalCost: price - 1 }), "SlippageExceeded");
+    await rejects(buy({ validUntil: Math.floor(Date.now() / 1000) - 10 }), "OrderExpired");
+    await rejects(buy({ buyerUsdc: sellerUsdcAta }), "InvalidTokenOwner");
+    await rejects(buy({ buyerFractionAta: buyerOtherAta.address }), "MintMismatch");
+
+    const balance = async (address: anchor.web3.PublicKey) =>
+      Number((await getAccount(provider.connection, address)).amount);
+    const before = {
+      buyer: await balance(defaults.buyerUsdc),
+      seller: await balance(sellerUsdcAta),
+      treasury: await balance(config.treasury),
+      sponsor: await balance(sponsorUsdc),
+    };
+    await buy({});
+
+    const protocolFee = Math.floor((price * config.protocolFeeBps) / 10_000);
+    assert.equal(before.buyer - (await balance(defaults.buyerUsdc)), price);
+    assert.equal((await balance(config.treasury)) - before.treasury, protocolFee);
+    assert.equal((await balance(sponsorUsdc)) - before.sponsor, 0);
+    assert.equal((await balance(sellerUsdcAta)) - before.seller, price - protocolFee);
+    assert.equal(await balance(defaults.buyerFractionAta), 45_000);
+  });
+
+  describe("listings and right of first refusal", () => {
+    let fx: PropertyFixture;
+    let config: ProtocolFixture;
+    let seller: web3.Keypair;
+    let holder: web3.Keypair; // Existing holder, allowed during the ROFR window
+    let outsider: web3.Keypair;
+    let sellerFractions: web3.PublicKey;
+    let listing: web3.PublicKey;
+    let escrow: web3.PublicKey;
+
+    const fill = async (buyer: web3.Keypair, amount: number) =>
+      program.methods
+        .fillListing(new BN(amount), new BN(1_000_000_000))
+        .accountsPartial({
+          propertyAccount: fx.property,
+          listing,
+          buyer: buyer.publicKey,
+          seller: seller.publicKey,
+          buyerUsdc: await tokenAccount(fx.usdcMint, buyer.publicKey, 100_000_000),
+          sellerUsdc: await tokenAccount(fx.usdcMint, seller.publicKey),
+          buyerFractionAta: await tokenAccount(fx.fractionMint, buyer.publicKey),
+          listingEscrow: escrow,
+          protocolConfig: config.protocolConfig,
+          treasuryUsdc: config.treasuryUsdc,
+          sponsorUsdc: fx.authorityUsdc,
+          priceOracle: pda("price_oracle", fx.property),
+          buyerHolder: holderPda(buyer.publicKey, fx.property),
+          sellerHolder: holderPda(seller.publicKey, fx.property),
+        })
+        .signers([buyer])
+        .rpc();
+
+    before(async () => {
+      config = await ensureProtocolConfig();
+      fx = await createProperty({ usdcMint: config.usdcMint });
+      seller = await fundedWallet();
+      holder = await fundedWallet();
+      outsider = await fundedWallet();
+
+      sellerFractions = await mintFractions(fx, seller.publicKey, 100_000);
+      await mintFractions(fx, holder.publicKey, 10_000);
+
+      listing = pda("listing", fx.property, seller.publicKey);
+      escrow = getAssociatedTokenAddressSync(fx.fractionMint, listing, true);
+
+      // Standing allowance for signed orders; listings must leave it alone
+      await approve(provider.connection, payer, sellerFractions, pda("order_delegate", fx.property), seller, 50_000);
+
+      await program.methods.setRofrWindow(new BN(3600)).accountsPartial({ propertyAccount: fx.property }).rpc();
+    });
+
+    it("escrows listed fractions without touching the order delegate allowance", async () => {
+      await program.methods
+        .createListing(new BN(40_000), new BN(1_000_000), new BN(Math.floor(Date.now() / 1000) + 3600))
+        .accountsPartial({
+          propertyAccount: fx.property,
+          seller: seller.publicKey,
+          sellerHolder: holderPda(seller.publicKey, fx.property),
+          sellerFractionAta: sellerFractions,
+          fractionMint: fx.fractionMint,
+          listing,
+          listingEscrow: escrow,
+        })
+        .signers([seller])
+        .rpc();
+
+      assert.equal((await tokenBalance(escrow)).toString(), "40000");
+      const sellerAta = await getAccount(provider.connection, sellerFractions);
+      assert.equal(sellerAta.amount.toString(), "60000");
+      assert.equal(sellerAta.delegatedAmount.toString(), "50000");
+
+      const sellerHolder = await program.account.holderState.fetch(holderPda(seller.publicKey, fx.property));
+      assert.equal(sellerHolder.balance.toNumber(), 100_000);
+      assert.equal(sellerHolder.listed.toNumber(), 40_000);
+    });
+
+    it("limits fills to existing holders during the ROFR window", async () => {
+      await expectError(fill(outsider, 10_000), "RofrWindowActive");
+
+      await fill(holder, 10_000);
+      assert.equal((await tokenBalance(escrow)).toString(), "30000");
+      const holderState = await program.account.holderState.fetch(holderPda(holder.publicKey, fx.property));
+      assert.equal(holderState.balance.toNumber(), 20_000);
+    });
+
+    it("rejects transfers to new holders while ROFR is enabled", async () => {
+      await expectError(
+        program.methods
+          .transferFractions(new BN(1_000))
+          .accountsPartial({
+            propertyAccount: fx.property,
+            sourceHolder: holderPda(seller.publicKey, fx.property),
+            destHolder: holderPda(outsider.publicKey, fx.property),
+            sourceFractionAta: sellerFractions,
+            destFractionAta: await tokenAccount(fx.fractionMint, outsider.publicKey),
+            sourceOwner: seller.publicKey,
+            destOwner: outsider.publicKey,
+          })
+          .signers([seller, outsider])
+          .rpc(),
+        "RofrRequiresListing"
+      );
+    });
+
+    it("returns escrowed fractions on cancel and keeps the allowance", async () => {
+      await program.methods
+        .cancelListing()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          seller: seller.publicKey,
+          listing,
+          listingEscrow: escrow,
+          sellerHolder: holderPda(seller.publicKey, fx.property),
+          sellerFractionAta: sellerFractions,
+        })
+        .signers([seller])
+        .rpc();
+
+      const sellerAta = await getAccount(provider.connection, sellerFractions);
+      assert.equal(sellerAta.amount.toString(), "90000");
+      assert.equal(sellerAta.delegatedAmount.toString(), "50000");
+      assert.isNull(await provider.connection.getAccountInfo(escrow));
+      assert.isNull(await program.account.listing.fetchNullable(listing));
+
+      const sellerHolder = await program.account.holderState.fetch(holderPda(seller.publicKey, fx.property));
+      assert.equal(sellerHolder.balance.toNumber(), 90_000);
+      assert.equal(sellerHolder.listed.toNumber(), 0);
+    });
+
+    it("lets existing holders buy directly by transfer during the ROFR window", async () => {
+      const direct = (destFractionAta: web3.PublicKey, sourceFractionAta = sellerFractions) =>
+        program.methods
+          .transferFractions(new BN(1_000))
+          .accountsPartial({
+            propertyAccount: fx.property,
+            sourceHolder: holderPda(seller.publicKey, fx.property),
+            destHolder: holderPda(holder.publicKey, fx.property),
+            sourceFractionAta,
+            destFractionAta,
+            sourceOwner: seller.publicKey,
+            destOwner: holder.publicKey,
+          })
+          .signers([seller, holder])
+          .rpc();
+
+      await expectError(direct(await tokenAccount(fx.fractionMint, outsider.publicKey)), "InvalidTokenOwner");
+      await expectError(direct(await tokenAccount(fx.usdcMint, holder.publicKey)), "MintMismatch");
+      await expectError(
+        direct(await tokenAccount(fx.fractionMint, holder.publicKey), await tokenAccount(fx.usdcMint, seller.publicKey)),
+        "MintMismatch"
+      );
+
+      await transferFractions(fx, seller, holder, 1_000);
+      const holderState = await program.account.holderState.fetch(holderPda(holder.publicKey, fx.property));
+      assert.equal(holderState.balance.toNumber(), 21_000);
+      assert.equal((await tokenBalance(await tokenAccount(fx.fractionMint, holder.publicKey))).toString(), "21000");
+    });
+  });
+
+  describe("price oracle", () => {
+    let fx: PropertyFixture;
+    let config: ProtocolFixture;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let priceOracle: web3.PublicKey;
+
+    const buy = async (from: web3.Keypair, to: web3.Keypair, amount: number, pricePerFraction: number) =>
+      program.methods
+        .buyFractions(new BN(amount), new BN(pricePerFraction), new BN(1_000_000_000), new BN(Math.floor(Date.now() / 1000) + 60))
+        .accountsPartial({
+          propertyAccount: fx.property,
+          buyer: to.publicKey,
+          seller: from.publicKey,
+          buyerUsdc: await tokenAccount(fx.usdcMint, to.publicKey, 100_000_000),
+          sellerUsdc: await tokenAccount(fx.usdcMint, from.publicKey),
+          buyerFractionAta: await tokenAccount(fx.fractionMint, to.publicKey),
+          sellerFractionAta: await tokenAccount(fx.fractionMint, from.publicKey),
+          protocolConfig: config.protocolConfig,
+          treasuryUsdc: config.treasuryUsdc,
+          sponsorUsdc: fx.authorityUsdc,
+          priceOracle,
+          buyerHolder: holderPda(to.publicKey, fx.property),
+          sellerHolder: holderPda(from.publicKey, fx.property),
+        })
+        .signers(from === to ? [from] : [from, to])
+        .rpc();
+
+    before(async () => {
+      config = await ensureProtocolConfig();
+      fx = await createProperty({ usdcMint: config.usdcMint });
+      seller = await fundedWallet();
+      buyer = await fundedWallet();
+      priceOracle = pda("price_oracle", fx.property);
+      await mintFractions(fx, seller.publicKey, 1_000_000);
+    });
+
+    it("rejects trades with oneself", async () => {
+      await expectError(buy(seller, seller, 100_000, 10_000_000), "SelfTrade");
+    });
+
+    it("records the price of a fill above the minimum notional", async () => {
+      await buy(seller, buyer, 500_000, 10_000_000); // 5 USDC
+      const oracle = await program.account.priceOracle.fetch(priceOracle);
+      assert.equal(oracle.lastPrice.toNumber(), 10_000_000);
+      assert.equal(oracle.len, 1);
+    });
+
+    it("counts dust fills as volume without moving the price", async () => {
+      await buy(seller, buyer, 1_000, 1_000); // 0.000001 USDC at a manipulated price
+      const oracle = await program.account.priceOracle.fetch(priceOracle);
+      assert.equal(oracle.lastPrice.toNumber(), 10_000_000);
+      assert.equal(oracle.len, 1);
+      assert.equal(oracle.tradeCount.toNumber(), 2);
+      assert.equal(oracle.totalVolume.toNumber(), 501_000);
+    });
+
+    it("takes at most one observation per interval", async () => {
+      const { paymentDecimals } = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(paymentDecimals, 6); // 1 USDC minimum notional
+
+      await buy(seller, buyer, 500_000, 12_000_000); // 6 USDC, right after the first fill
+      const oracle = await program.account.priceOracle.fetch(priceOracle);
+      assert.equal(oracle.lastPrice.toNumber(), 12_000_000);
+      assert.equal(oracle.len, 1);
+      assert.equal(oracle.observations[oracle.head].pricePerFraction.toNumber(), 10_000_000);
+    });
+
+    it("returns the TWAP of qualifying fills", async () => {
+      await sleep(3_000);
+      const twap = await program.methods
+        .getTwap(new BN(2))
+        .accountsPartial({ propertyAccount: fx.property, priceOracle })
+        .view();
+      assert.equal(twap.toNumber(), 10_000_000);
+    });
+  });
+
+  describe("signed sell orders", () => {
+    let fx: PropertyFixture;
+    let config: ProtocolFixture;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let sellerFractions: web3.PublicKey;
+    let buyerFractions: web3.PublicKey;
+    let sellerUsdc: web3.PublicKey;
+    let buyerUsdc: web3.PublicKey;
+    let orderDelegate: web3.PublicKey;
+
+    const order = (nonce: number) => ({
+      property: fx.property,
+      seller: seller.publicKey,
+      fractionAmount: new BN(10_000),
+      pricePerFraction: new BN(100_000_000), // 100 USDC per whole fraction
+      expiry: new BN(Math.floor(Date.now() / 1000) + 600),
+      nonce: new BN(nonce),
+    });
+
+    // SELL_ORDER_DOMAIN followed by the borsh-encoded SellOrder
+    const message = (o: ReturnType<typeof order>) =>
+      Buffer.concat([
+        Buffer.from("rwa:sell_order:v1"),
+        o.property.toBuffer(),
+        o.seller.toBuffer(),
+        u64(o.fractionAmount),
+        u64(o.pricePerFraction),
+        u64(o.expiry),
+        u64(o.nonce),
+      ]);
+
+    const fill = (o: ReturnType<typeof order>, signed: Buffer | null = message(o)) =>
+      program.methods
+        .executeSignedOrder(o, new BN(10_000_000))
+        .accountsPartial({
+          propertyAccount: fx.property,
+          buyer: buyer.publicKey,
+          seller: seller.publicKey,
+          buyerUsdc,
+          sellerUsdc,
+          buyerFractionAta: buyerFractions,
+          sellerFractionAta: sellerFractions,
+          orderDelegate,
+          protocolConfig: config.protocolConfig,
+          treasuryUsdc: config.treasuryUsdc,
+          sponsorUsdc: fx.authorityUsdc,
+          priceOracle: pda("price_oracle", fx.property),
+          buyerHolder: holderPda(buyer.publicKey, fx.property),
+          sellerHolder: holderPda(seller.publicKey, fx.property),
+          orderNonce: pda("order_nonce", seller.publicKey, u64(o.nonce)),
+          instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions(
+          signed ? [web3.Ed25519Program.createInstructionWithPrivateKey({ privateKey: seller.secretKey, message: signed })] : []
+        )
+        .signers([buyer])
+        .rpc();
+
+    before(async () => {
+      config = await ensureProtocolConfig();
+      fx = await createProperty({ usdcMint: config.usdcMint });
+      seller = await fundedWallet();
+      buyer = await fundedWallet();
+
+      sellerFractions = await mintFractions(fx, seller.publicKey, 100_000);
+      buyerFractions = await tokenAccount(fx.fractionMint, buyer.publicKey);
+      sellerUsdc = await tokenAccount(fx.usdcMint, seller.publicKey);
+      buyerUsdc = await tokenAccount(fx.usdcMint, buyer.publicKey, 1_000_000_000);
+
+      orderDelegate = pda("order_delegate", fx.property);
+      await approve(provider.connection, payer, sellerFractions, orderDelegate, seller, 100_000);
+    });
+
+    it("fills an order signed off-chain and marks its nonce used", async () => {
+      await fill(order(1));
+
+      assert.equal((await tokenBalance(buyerFractions)).toString(), "10000");
+      assert.ok((await tokenBalance(sellerUsdc)) > BigInt(0));
+      const sellerHolder = await program.account.holderState.fetch(holderPda(seller.publicKey, fx.property));
+      assert.equal(sellerHolder.balance.toNumber(), 90_000);
+      const nonce = await program.account.orderNonce.fetch(pda("order_nonce", seller.publicKey, u64(1)));
+      assert.ok(nonce.filledAt.toNumber() > 0);
+    });
+
+    it("rejects a replay of a filled nonce", async () => {
+      await expectError(fill(order(1)), "already in use");
+      assert.equal((await tokenBalance(buyerFractions)).toString(), "10000");
+    });
+
+    it("rejects an order the seller did not sign", async () => {
+      const signed = order(2);
+      const tampered = { ...signed, pricePerFraction: new BN(1) };
+      await expectError(fill(tampered, message(signed)), "InvalidSignatureVerification");
+    });
+
+    it("rejects an order without an ed25519 instruction", async () => {
+      await expectError(fill(order(3), null), "MissingSignatureVerification");
+    });
+  });
+
+  describe("read-only quotes", () => {
+    let fx: PropertyFixture;
+    let config: ProtocolFixture;
+    let alice: web3.Keypair;
+
+    const holderView = () => ({
+      propertyAccount: fx.property,
+      holderWallet: alice.publicKey,
+      holderState: holderPda(alice.publicKey, fx.property),
+    });
+
+    before(async () => {
+      config = await ensureProtocolConfig();
+      fx = await createProperty();
+      alice = await fundedWallet();
+      const bob = await fundedWallet();
+      await mintFractions(fx, alice.publicKey, 600);
+      await mintFractions(fx, bob.publicKey, 400);
+      await depositRent(fx, 1_000);
+    });
+
+    it("reports the pending rent a claim pays", async () => {
+      const pending = await program.methods.getPendingRent().accountsPartial(holderView()).view();
+      assert.equal(pending.toNumber(), 600);
+
+      const position = await program.methods.getHolderPosition().accountsPartial(holderView()).view();
+      assert.equal(position.balance.toNumber(), 600);
+      assert.equal(position.mintedFractions.toNumber(), 1_000);
+      assert.equal(position.pendingRent.toNumber(), 600);
+      assert.equal(position.pendingScrip.toNumber(), 0);
+
+      assert.equal((await tokenBalance(await claimRent(fx, alice))).toString(), pending.toString());
+      assert.equal((await program.methods.getPendingRent().accountsPartial(holderView()).view()).toNumber(), 0);
+    });
+
+    it("only reads the holder state of the given wallet", async () => {
+      const bob = await fundedWallet();
+      await mintFractions(fx, bob.publicKey, 1);
+      await expectError(
+        program.methods
+          .getPendingRent()
+          .accountsPartial({ ...holderView(), holderState: holderPda(bob.publicKey, fx.property) })
+          .view(),
+        "ConstraintSeeds"
+      );
+    });
+
+    it("quotes trade fees", async () => {
+      await program.methods.setSponsorFee(100).accountsPartial({ propertyAccount: fx.property }).rpc();
+      const { protocolFeeBps, maxProtocolFee } = await program.account.protocolConfig.fetch(config.protocolConfig);
+
+      // 2 whole fractions at 1 USDC
+      const quote = await program.methods
+        .quoteTrade(new BN(2_000_000), new BN(1_000_000), web3.Keypair.generate().publicKey, alice.publicKey)
+        .accountsPartial({ propertyAccount: fx.property, protocolConfig: config.protocolConfig })
+        .view();
+
+      let protocolFee = Math.floor((2_000_000 * protocolFeeBps) / 10_000);
+      if (maxProtocolFee.gtn(0)) {
+        protocolFee = Math.min(protocolFee, maxProtocolFee.toNumber());
+      }
+      assert.equal(quote.price.toNumber(), 2_000_000);
+      assert.equal(quote.sponsorFee.toNumber(), 20_000);
+      assert.equal(quote.protocolFee.toNumber(), protocolFee);
+      assert.equal(quote.sellerProceeds.toNumber(), 2_000_000 - 20_000 - protocolFee);
+
+      const max = new BN("18446744073709551615");
+      await expectError(
+        program.methods
+          .quoteTrade(max, max, web3.Keypair.generate().publicKey, alice.publicKey)
+          .accountsPartial({ propertyAccount: fx.property, protocolConfig: config.protocolConfig })
+          .view(),
+        "NumericOverflow"
+      );
+    });
+  });
+
+  describe("closing holder states", () => {
+    let fx: PropertyFixture;
+    let alice: web3.Keypair;
+    let bob: web3.Keypair;
+
+    const close = (holder: web3.Keypair) =>
+      program.methods
+        .closeHolderState()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holder: holder.publicKey,
+          holderState: holderPda(holder.publicKey, fx.property),
+        })
+        .signers([holder])
+        .rpc();
+
+    before(async () => {
+      fx = await createProperty();
+      alice = await fundedWallet();
+      bob = await fundedWallet();
+      await mintFractions(fx, alice.publicKey, 500);
+      await mintFractions(fx, bob.publicKey, 500);
+      await depositRent(fx, 1_000);
+      await transferFractions(fx, alice, bob, 500);
+    });
+
+    it("refuses to close while rent is claimable", async () => {
+      await expectError(close(alice), "HolderNotEmpty");
+    });
+
+    it("refunds the lamports once empty", async () => {
+      assert.equal((await tokenBalance(await claimRent(fx, alice))).toString(), "500");
+
+      const before = await provider.connection.getBalance(alice.publicKey);
+      await close(alice);
+      assert.isAbove(await provider.connection.getBalance(alice.publicKey), before);
+      assert.isNull(await program.account.holderState.fetchNullable(holderPda(alice.publicKey, fx.property)));
+    });
+
+    it("re-creates a closed state without back rent", async () => {
+      await transferFractions(fx, bob, alice, 200);
+      await depositRent(fx, 1_000);
+
+      // 500 earlier payout plus 200 of the new deposit
+      assert.equal((await tokenBalance(await claimRent(fx, alice))).toString(), "700");
+      assert.equal((await tokenBalance(await claimRent(fx, bob))).toString(), "1300");
+    });
+  });
+
+  describe("holder registration and sync", () => {
+    let fx: PropertyFixture;
+    let alice: web3.Keypair; // Minted through the program
+    let bob: web3.Keypair; // Receives fractions by plain SPL transfer
+    let carol: web3.Keypair; // Registers without fractions
+    let aliceFractions: web3.PublicKey;
+
+    const register = (holder: web3.Keypair, holderFractionAta: web3.PublicKey) =>
+      program.methods
+        .registerHolder()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holder: holder.publicKey,
+          holderState: holderPda(holder.publicKey, fx.property),
+          holderFractionAta,
+        })
+        .signers([holder])
+        .rpc();
+
+    const sync = (wallet: web3.PublicKey, holderFractionAta: web3.PublicKey) =>
+      program.methods
+        .syncHolder()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holderWallet: wallet,
+          holderState: holderPda(wallet, fx.property),
+          holderFractionAta,
+        })
+        .rpc();
+
+    const balanceOf = async (wallet: web3.PublicKey) =>
+      (await program.account.holderState.fetch(holderPda(wallet, fx.property))).balance.toNumber();
+
+    before(async () => {
+      fx = await createProperty();
+      alice = await fundedWallet();
+      bob = await fundedWallet();
+      carol = await fundedWallet();
+      aliceFractions = await mintFractions(fx, alice.publicKey, 600);
+    });
+
+    it("registers a wallet without fractions at zero balance", async () => {
+      await register(carol, await tokenAccount(fx.fractionMint, carol.publicKey));
+
+      const state = await program.account.holderState.fetch(holderPda(carol.publicKey, fx.property));
+      assert.ok(state.initialized);
+      assert.ok(state.holder.equals(carol.publicKey));
+      assert.equal(state.balance.toNumber(), 0);
+      assert.equal((await program.account.propertyAccount.fetch(fx.property)).trackedBalance.toNumber(), 600);
+    });
+
+    it("rejects a second registration", async () => {
+      await expectError(register(carol, await tokenAccount(fx.fractionMint, carol.publicKey)), "already in use");
+    });
+
+    it("only reads the holder's own fraction ATA", async () => {
+      await expectError(register(bob, aliceFractions), "ConstraintTokenOwner");
+      await expectError(register(bob, await tokenAccount(fx.usdcMint, bob.publicKey)), "ConstraintAssociated");
+      await expectError(sync(alice.publicKey, await tokenAccount(fx.fractionMint, carol.publicKey)), "ConstraintTokenOwner");
+    });
+
+    it("follows an SPL transfer outside the program once the sender is synced first", async () => {
+      const bobFractions = await tokenAccount(fx.fractionMint, bob.publicKey);
+      await register(bob, bobFractions);
+      await splTransfer(provider.connection, payer, aliceFractions, bobFractions, alice, 200);
+
+      // Alice's 600 are still tracked, so bob cannot claim the 200 yet
+      await expectError(sync(bob.publicKey, bobFractions), "UntrackedSupplyExceeded");
+      await sync(alice.publicKey, aliceFractions);
+      await sync(bob.publicKey, bobFractions);
+
+      assert.equal(await balanceOf(alice.publicKey), 400);
+      assert.equal(await balanceOf(bob.publicKey), 200);
+      assert.equal((await program.account.propertyAccount.fetch(fx.property)).trackedBalance.toNumber(), 600);
+
+      await depositRent(fx, 600);
+      assert.equal((await tokenBalance(await claimRent(fx, alice))).toString(), "400");
+      assert.equal((await tokenBalance(await claimRent(fx, bob))).toString(), "200");
+    });
+  });
 });
@@ -1,5 +1,5 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program, web3 } from "@coral-xyz/anchor";
+import { Program, web3, BN } from "@coral-xyz/anchor";
 import { Rwa } from "../target/types/rwa";
 import {
   createMint,
@@ -7,8 +7,30 @@ import {
   mintTo,
   getAccount,
   TOKEN_PROGRAM_ID,
+  burn,
 } from "@solana/spl-token";
 import { assert } from "chai";
+import {
+  payer,
+  sleep,
+  u64,
+  pda,
+  holderPda,
+  taxYearPda,
+  fundedWallet,
+  tokenAccount,
+  tokenBalance,
+  expectError,
+  createProperty,
+  mintFractions,
+  depositRent,
+  claimRent,
+  transferFractions,
+  transferFractionsIx,
+  crankAccounts,
+  PropertyFixture,
+  ensureProtocolConfig,
+} from "./utils";
 
 describe("rwa", () => {
   const provider = anchor.AnchorProvider.env();
@@ -26,6 +48,16 @@ describe("rwa", () => {
   let nftVault: web3.PublicKey;
   let rentVaultAta: web3.PublicKey;
   let nftVaultAta: web3.PublicKey;
+  let taxVault: web3.PublicKey;
+  let taxVaultAta: web3.PublicKey;
+  const taxYearRecord = () => {
+    const year = Buffer.alloc(2);
+    year.writeUInt16LE(new Date().getUTCFullYear());
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("tax_year"), propertyAccount.publicKey.toBuffer(), authority.toBuffer(), year],
+      program.programId
+    )[0];
+  };
 
   before(async () => {
     console.log("Setting up test environment...");
@@ -87,6 +119,19 @@ describe("rwa", () => {
     );
     nftVaultAta = nftVaultAccount.address;
 
+    [taxVault] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("tax_vault"), propertyAccount.publicKey.toBuffer()],
+      program.programId
+    );
+    const taxVaultAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      usdcMint,
+      taxVault,
+      true
+    );
+    taxVaultAta = taxVaultAccount.address;
+
     const metadataUri = "https://ipfs.io/ipfs/QmExample123";
     const uriBytes = Buffer.from(metadataUri);
     const paddedUri = Buffer.concat([uriBytes, Buffer.alloc(200 - uriBytes.length)]);
@@ -133,6 +178,10 @@ describe("rwa", () => {
     );
 
     const amount = new anchor.BN(100_000);
+    const [destinationHolder] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("holder"), authority.toBuffer(), propertyAccount.publicKey.toBuffer()],
+      program.programId
+    );
 
     await program.methods
       .mintFractions(amount)
@@ -141,12 +190,17 @@ describe("rwa", () => {
         fractionMint: fractionMint.publicKey,
         mintAuthority,
         destination: destination.address,
+        destinationHolder,
       })
       .rpc();
 
     const tokenAccount = await getAccount(provider.connection, destination.address);
     assert.equal(tokenAccount.amount.toString(), "100000");
 
+    const holder = await program.account.holderState.fetch(destinationHolder);
+    assert.equal(holder.balance.toString(), "100000");
+    assert.ok(holder.initialized);
+
     console.log("✅ Fractions minted");
   });
 
@@ -156,13 +210,6 @@ describe("rwa", () => {
       program.programId
     );
 
-    const holderFractionAta = await getOrCreateAssociatedTokenAccount(
-      provider.connection,
-      provider.wallet.payer,
-      fractionMint.publicKey,
-      authority
-    );
-
     const holderUsdcAta = await getOrCreateAssociatedTokenAccount(
       provider.connection,
       provider.wallet.payer,
@@ -170,23 +217,26 @@ describe("rwa", () => {
       authority
     );
 
-    // First claim initializes holder_state with current cum_rent_per_share (which is 0)
+    // holder_state was created by mint_fractions at cum_rent_per_share 0; nothing to claim yet
     await program.methods
       .claimRent()
       .accountsPartial({
         propertyAccount: propertyAccount.publicKey,
+        holderWallet: authority,
         holderState,
         payer: authority,
-        holderFractionAta: holderFractionAta.address,
         receiverUsdc: holderUsdcAta.address,
         rentVault,
         rentVaultAta,
+        taxVaultAta,
+        taxYearRecord: taxYearRecord(),
       })
       .rpc();
 
     const holderStateAccount = await program.account.holderState.fetch(holderState);
     assert.equal(holderStateAccount.balance.toString(), "100000");
     assert.equal(holderStateAccount.rewardDebt.toString(), "0"); // cum_rent_per_share was 0
+    assert.isNull(await provider.connection.getAccountInfo(taxYearRecord())); // nothing paid, nothing recorded
 
     console.log("✅ Holder state initialized");
   });
@@ -216,13 +266,36 @@ describe("rwa", () => {
     const propertyBefore = await program.account.propertyAccount.fetch(propertyAccount.publicKey);
     console.log("cum_rent_per_share BEFORE deposit:", propertyBefore.cumRentPerShare.toString());
 
+    const [reserveVault] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reserve_vault"), propertyAccount.publicKey.toBuffer()],
+      program.programId
+    );
+    const reserveVaultAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      usdcMint,
+      reserveVault,
+      true
+    );
+
+    const [distributionEpoch] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("epoch"), propertyAccount.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
     await program.methods
-      .depositRent(depositAmount)
+      .depositRent(depositAmount, new anchor.BN(0), Array.from(Buffer.alloc(32)))
       .accountsPartial({
         propertyAccount: propertyAccount.publicKey,
         payer: authority,
+        rentPayer: null,
+        distributionEpoch,
         payerUsdc: payerUsdc.address,
+        rentVault,
         rentVaultAta,
+        reserveVault,
+        reserveVaultAta: reserveVaultAta.address,
+        sponsorUsdc: payerUsdc.address,
       })
       .rpc();
 
@@ -277,12 +350,14 @@ describe("rwa", () => {
       .claimRent()
       .accountsPartial({
         propertyAccount: propertyAccount.publicKey,
+        holderWallet: authority,
         holderState,
         payer: authority,
-        holderFractionAta: holderFractionAta.address,
         receiverUsdc: holderUsdcAta.address,
         rentVault,
         rentVaultAta,
+        taxVaultAta,
+        taxYearRecord: taxYearRecord(),
       })
       .rpc();
 
@@ -299,4 +374,1331 @@ describe("rwa", () => {
     assert.ok(claimed > BigInt(0), `Should have claimed rent, but claimed ${claimed}`);
     console.log("✅ Rent claimed:", claimed.toString());
   });
+
+  describe("rent accounting", () => {
+    describe("rounding remainder", () => {
+      let fx: PropertyFixture;
+
+      before(async () => {
+        fx = await createProperty();
+        await mintFractions(fx, authority, 3);
+      });
+
+      it("carries the division remainder into the next deposit", async () => {
+        await depositRent(fx, 10);
+        let property = await program.account.propertyAccount.fetch(fx.property);
+        // 10e9 / 3 = 3_333_333_333 rem 1
+        assert.equal(property.cumRentPerShare.toString(), "3333333333");
+        assert.equal(property.undistributedRemainder.toString(), "1");
+
+        await depositRent(fx, 10);
+        property = await program.account.propertyAccount.fetch(fx.property);
+        // (10e9 + 1) / 3 = 3_333_333_333 rem 2
+        assert.equal(property.cumRentPerShare.toString(), "6666666666");
+        assert.equal(property.undistributedRemainder.toString(), "2");
+
+        await depositRent(fx, 10);
+        property = await program.account.propertyAccount.fetch(fx.property);
+        // (10e9 + 2) / 3 = 3_333_333_334 rem 0: nothing is lost across deposits
+        assert.equal(property.cumRentPerShare.toString(), "10000000000");
+        assert.equal(property.undistributedRemainder.toString(), "0");
+      });
+    });
+
+    describe("guards", () => {
+      it("rejects rent on a property without fractions", async () => {
+        await expectError(depositRent(await createProperty(), 1_000), "NoFractions");
+      });
+
+      it("rejects a negative stream period", async () => {
+        const fx = await createProperty();
+        await expectError(
+          program.methods.setRentStreamPeriod(new BN(-1)).accountsPartial({ propertyAccount: fx.property }).rpc(),
+          "InvalidAmount"
+        );
+      });
+    });
+
+    describe("streamed rent", () => {
+      let fx: PropertyFixture;
+
+      before(async () => {
+        fx = await createProperty();
+        await mintFractions(fx, authority, 1_000_000);
+        await program.methods.setRentStreamPeriod(new BN(100)).accountsPartial({ propertyAccount: fx.property }).rpc();
+      });
+
+      it("releases a deposit over the stream period", async () => {
+        await depositRent(fx, 1_000_000);
+        const property = await program.account.propertyAccount.fetch(fx.property);
+        assert.equal(property.streamEnd.toNumber() - property.streamLastUpdate.toNumber(), 100);
+        assert.equal(property.streamPending.toString(), "1000000000000000"); // 1 USDC * SCALE
+      });
+
+      it("does not restart the stream for a small top-up", async () => {
+        const before = await program.account.propertyAccount.fetch(fx.property);
+        await sleep(2_000);
+        await depositRent(fx, 1);
+
+        const after = await program.account.propertyAccount.fetch(fx.property);
+        assert.isAtMost(after.streamEnd.toNumber(), before.streamEnd.toNumber());
+        assert.ok(after.cumRentPerShare.gt(before.cumRentPerShare), "rent streamed in the meantime was released");
+      });
+
+      it("weights the stream end by the new amount", async () => {
+        const before = await program.account.propertyAccount.fetch(fx.property);
+        await depositRent(fx, 1_000_000);
+
+        const after = await program.account.propertyAccount.fetch(fx.property);
+        const at = after.streamLastUpdate.toNumber();
+        const oldRemaining = before.streamEnd.toNumber() - at;
+        // Pending amounts are roughly equal, so the end lands about halfway
+        const expected = at + Math.floor((oldRemaining + 100) / 2);
+        assert.approximately(after.streamEnd.toNumber(), expected, 2);
+        assert.isBelow(after.streamEnd.toNumber(), at + 100);
+      });
+    });
+  });
+
+  describe("authorized rent payers", () => {
+    let fx: PropertyFixture;
+    let tenant: web3.Keypair;
+    let tenantUsdc: web3.PublicKey;
+
+    const reference = Buffer.alloc(32);
+    Buffer.from("lease-7/2026-10").copy(reference);
+
+    const tenantDeposit = async (payerUsdc: web3.PublicKey, registered: boolean, recordDate = 0) => {
+      const { epochCount } = await program.account.propertyAccount.fetch(fx.property);
+      const distributionEpoch = pda("epoch", fx.property, u64(epochCount));
+      await program.methods
+        .depositRent(new BN(1_000), new BN(recordDate), Array.from(reference))
+        .accountsPartial({
+          propertyAccount: fx.property,
+          payer: tenant.publicKey,
+          rentPayer: registered ? pda("rent_payer", fx.property, tenant.publicKey) : null,
+          distributionEpoch,
+          payerUsdc,
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+          reserveVault: fx.reserveVault,
+          reserveVaultAta: fx.reserveVaultAta,
+          sponsorUsdc: fx.authorityUsdc,
+        })
+        .signers([tenant])
+        .rpc();
+      return distributionEpoch;
+    };
+
+    before(async () => {
+      fx = await createProperty();
+      tenant = await fundedWallet();
+      tenantUsdc = await tokenAccount(fx.usdcMint, tenant.publicKey, 10_000);
+      await mintFractions(fx, authority, 1_000);
+    });
+
+    it("rejects deposits from unregistered wallets", async () => {
+      await expectError(tenantDeposit(tenantUsdc, false), "UnauthorizedPayer");
+    });
+
+    it("records the payer and reference of a registered tenant's deposit", async () => {
+      const label = Buffer.alloc(32);
+      Buffer.from("Tenant unit 4B").copy(label);
+      await program.methods
+        .addRentPayer(tenant.publicKey, Array.from(label))
+        .accountsPartial({ propertyAccount: fx.property, rentPayer: pda("rent_payer", fx.property, tenant.publicKey) })
+        .rpc();
+
+      const epoch = await program.account.distributionEpoch.fetch(await tenantDeposit(tenantUsdc, true));
+      assert.ok(epoch.payer.equals(tenant.publicKey));
+      assert.deepEqual(Buffer.from(epoch.reference), reference);
+      assert.equal(epoch.netAmount.toNumber(), 1_000);
+    });
+
+    it("leaves future record dates to the authority and manager", async () => {
+      const future = Math.floor(Date.now() / 1000) + 3600;
+      await expectError(tenantDeposit(tenantUsdc, true, future), "RecordDateRestricted");
+
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.ok(property.epochPending.eqn(0));
+    });
+
+    it("rejects USDC accounts of another mint", async () => {
+      const otherMint = await createMint(provider.connection, payer, authority, null, 6);
+      await expectError(tenantDeposit(await tokenAccount(otherMint, tenant.publicKey, 1_000), true), "MintMismatch");
+    });
+
+    it("stops accepting a removed payer", async () => {
+      await program.methods
+        .removeRentPayer()
+        .accountsPartial({ propertyAccount: fx.property, rentPayer: pda("rent_payer", fx.property, tenant.publicKey) })
+        .rpc();
+      await expectError(tenantDeposit(tenantUsdc, false), "UnauthorizedPayer");
+    });
+  });
+
+  describe("distribution epochs and record dates", () => {
+    let fx: PropertyFixture;
+    let alice: web3.Keypair;
+    let bob: web3.Keypair;
+
+    const now = () => Math.floor(Date.now() / 1000);
+
+    const applyPendingEpoch = async () => {
+      const { epochCount } = await program.account.propertyAccount.fetch(fx.property);
+      return program.methods
+        .applyPendingEpoch()
+        .accountsPartial({ propertyAccount: fx.property, distributionEpoch: pda("epoch", fx.property, u64(epochCount.subn(1))) })
+        .rpc();
+    };
+
+    before(async () => {
+      fx = await createProperty();
+      alice = await fundedWallet();
+      bob = await fundedWallet();
+      await mintFractions(fx, alice.publicKey, 1_000);
+    });
+
+    it("rejects record dates beyond the maximum delay", async () => {
+      await expectError(depositRent(fx, 1_000, now() + 91 * 86_400), "RecordDateTooFar");
+    });
+
+    it("pays the epoch to holders of record at the record date", async () => {
+      const epoch = await depositRent(fx, 1_000, now() + 4);
+      let property = await program.account.propertyAccount.fetch(fx.property);
+      assert.ok(property.epochPending.gtn(0));
+      assert.equal(property.cumRentPerShare.toString(), "0");
+      await expectError(depositRent(fx, 1_000), "EpochPending");
+
+      // Bob becomes a holder of record before the record date
+      await transferFractions(fx, alice, bob, 500);
+
+      await sleep(5_000);
+      assert.equal((await tokenBalance(await claimRent(fx, alice))).toString(), "500");
+      assert.equal((await tokenBalance(await claimRent(fx, bob))).toString(), "500");
+
+      property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.epochCredited.toNumber(), 1);
+      const epochAccount = await program.account.distributionEpoch.fetch(epoch);
+      assert.equal(epochAccount.netAmount.toNumber(), 1_000);
+    });
+
+    it("lets the authority apply a pending epoch early", async () => {
+      const epoch = await depositRent(fx, 2_000, now() + 3_600);
+      await applyPendingEpoch();
+
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.epochPending.toString(), "0");
+      assert.equal(property.epochCredited.toNumber(), 2);
+      assert.equal(property.cumRentPerShare.toString(), "3000000000");
+      const epochAccount = await program.account.distributionEpoch.fetch(epoch);
+      assert.isAtMost(epochAccount.recordDate.toNumber(), now() + 5);
+
+      await expectError(applyPendingEpoch(), "NoPendingEpoch");
+      await depositRent(fx, 1_000);
+    });
+  });
+
+  describe("rent fees", () => {
+    let fx: PropertyFixture;
+
+    const setFees = (managementBps: number, performanceBps: number, hurdle: number, window: number) =>
+      program.methods
+        .setRentFees(managementBps, performanceBps, new BN(hurdle), new BN(window))
+        .accountsPartial({ propertyAccount: fx.property })
+        .rpc();
+
+    // The authority both pays the rent and receives the sponsor fees
+    const depositNet = async (amount: number) => {
+      const before = await tokenBalance(fx.authorityUsdc);
+      await depositRent(fx, amount);
+      return Number(before - (await tokenBalance(fx.authorityUsdc)));
+    };
+
+    before(async () => {
+      fx = await createProperty();
+      await mintFractions(fx, authority, 1_000);
+    });
+
+    it("caps the fee terms", async () => {
+      await expectError(setFees(2_001, 0, 0, 0), "FeeTooHigh");
+      await expectError(setFees(0, 3_001, 0, 0), "FeeTooHigh");
+    });
+
+    it("charges the performance fee only above the hurdle", async () => {
+      await setFees(200, 1_000, 500, 3_600); // 2% management, 10% over 500 per hour
+
+      // 20 management; 480 of the remaining 980 is above the hurdle, so 48 performance
+      assert.equal(await depositNet(1_000), 932);
+      let property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.cumRentPerShare.toString(), "932000000");
+      assert.equal(property.perfWindowDistributed.toNumber(), 980);
+
+      // The hurdle is already used up in this window, so all 980 pays the performance fee
+      assert.equal(await depositNet(1_000), 882);
+      property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.cumRentPerShare.toString(), "1814000000");
+    });
+  });
+
+  describe("maintenance reserve", () => {
+    let fx: PropertyFixture;
+
+    const setPolicy = (bps: number, cap: number) =>
+      program.methods.setReservePolicy(bps, new BN(cap)).accountsPartial({ propertyAccount: fx.property }).rpc();
+
+    const releaseExcess = () =>
+      program.methods
+        .releaseExcessReserve()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          reserveVault: fx.reserveVault,
+          reserveVaultAta: fx.reserveVaultAta,
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+        })
+        .rpc();
+
+    before(async () => {
+      fx = await createProperty();
+      await mintFractions(fx, authority, 1_000);
+      await setPolicy(1_000, 150); // 10% up to 150
+    });
+
+    it("withholds the reserve share until the cap is reached", async () => {
+      await depositRent(fx, 1_000);
+      await depositRent(fx, 1_000); // Only 50 of the 100 fit under the cap
+
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.reserveBalance.toNumber(), 150);
+      assert.equal((await tokenBalance(fx.reserveVaultAta)).toString(), "150");
+      assert.equal(property.cumRentPerShare.toString(), "1850000000"); // (900 + 950) / 1000 fractions
+    });
+
+    it("has nothing to release while deposits respect the cap", async () => {
+      await expectError(releaseExcess(), "NothingToSweep");
+    });
+
+    it("releases the excess to holders after the cap is lowered", async () => {
+      await setPolicy(1_000, 100);
+      await releaseExcess();
+
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.reserveBalance.toNumber(), 100);
+      assert.equal((await tokenBalance(fx.reserveVaultAta)).toString(), "100");
+      assert.equal(property.cumRentPerShare.toString(), "1900000000");
+    });
+  });
+
+  describe("expense ledger", () => {
+    let fx: PropertyFixture;
+    let manager: web3.Keypair;
+    let holder: web3.Keypair;
+    let payeeUsdc: web3.PublicKey;
+    let expense: web3.PublicKey;
+
+    const recordExpense = (signer: web3.Keypair, amount: number) =>
+      program.methods
+        .recordExpense({ maintenance: {} }, new BN(amount), Array.from(Buffer.alloc(32, 7)))
+        .accountsPartial({ propertyAccount: fx.property, manager: signer.publicKey, expense, payeeUsdc })
+        .signers([signer])
+        .rpc();
+
+    const payExpense = () =>
+      program.methods
+        .payExpense()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          manager: manager.publicKey,
+          expense,
+          payeeUsdc,
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+        })
+        .signers([manager])
+        .rpc();
+
+    before(async () => {
+      fx = await createProperty();
+      manager = await fundedWallet();
+      holder = await fundedWallet();
+      payeeUsdc = await tokenAccount(fx.usdcMint, web3.Keypair.generate().publicKey);
+      expense = pda("expense", fx.property, u64(0));
+      await mintFractions(fx, holder.publicKey, 1_000);
+      await program.methods.setPropertyManager(manager.publicKey).accountsPartial({ propertyAccount: fx.property }).rpc();
+    });
+
+    it("only lets the manager record expenses", async () => {
+      await expectError(recordExpense(holder, 300), "Unauthorized");
+      await recordExpense(manager, 300);
+
+      const record = await program.account.expenseRecord.fetch(expense);
+      assert.equal(record.amount.toNumber(), 300);
+      assert.deepEqual(record.payee, payeeUsdc);
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.expensesPayable.toNumber(), 300);
+    });
+
+    it("cannot pay an expense before rent funds it", async () => {
+      await expectError(payExpense(), "InsufficientExpenseFunds");
+    });
+
+    it("withholds recorded expenses from gross rent", async () => {
+      await depositRent(fx, 1_000);
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.expensesPayable.toNumber(), 0);
+      assert.equal(property.expenseFunds.toNumber(), 300);
+      assert.equal(property.cumRentPerShare.toString(), "700000000"); // 700 net over 1000 fractions
+    });
+
+    it("pays the expense once and holders receive the net", async () => {
+      await payExpense();
+      assert.equal((await tokenBalance(payeeUsdc)).toString(), "300");
+      await expectError(payExpense(), "ExpenseAlreadyPaid");
+
+      const receiver = await claimRent(fx, holder);
+      assert.equal((await tokenBalance(receiver)).toString(), "700");
+      assert.equal((await tokenBalance(fx.rentVaultAta)).toString(), "0");
+    });
+
+    describe("cancellation", () => {
+      const expenseAt = (id: number) => pda("expense", fx.property, u64(id));
+
+      const record = (id: number, amount: number, payee = payeeUsdc) =>
+        program.methods
+          .recordExpense({ other: {} }, new BN(amount), Array.from(Buffer.alloc(32, 8)))
+          .accountsPartial({ propertyAccount: fx.property, manager: manager.publicKey, expense: expenseAt(id), payeeUsdc: payee })
+          .signers([manager])
+          .rpc();
+
+      const cancel = (id: number, signer?: web3.Keypair) =>
+        program.methods
+          .cancelExpense()
+          .accountsPartial({ propertyAccount: fx.property, signer: signer ? signer.publicKey : authority, expense: expenseAt(id) })
+          .signers(signer ? [signer] : [])
+          .rpc();
+
+      it("only records payees in the payment mint", async () => {
+        await expectError(record(1, 100, await tokenAccount(fx.fractionMint, holder.publicKey)), "MintMismatch");
+      });
+
+      it("credits withheld funds of a cancelled expense to holders", async () => {
+        await record(1, 400);
+        await depositRent(fx, 1_000);
+        let property = await program.account.propertyAccount.fetch(fx.property);
+        assert.equal(property.expenseFunds.toNumber(), 400);
+
+        await expectError(cancel(1, holder), "Unauthorized");
+        await cancel(1);
+        property = await program.account.propertyAccount.fetch(fx.property);
+        assert.equal(property.expenseFunds.toNumber(), 0);
+        assert.equal(property.expensesPayable.toNumber(), 0);
+        assert.deepEqual((await program.account.expenseRecord.fetch(expenseAt(1))).status, { cancelled: {} });
+
+        // 600 net of the deposit plus the 400 released
+        const before = await tokenBalance(await tokenAccount(fx.usdcMint, holder.publicKey));
+        const receiver = await claimRent(fx, holder);
+        assert.equal(Number((await tokenBalance(receiver)) - before), 1_000);
+
+        await expectError(cancel(1), "ExpenseCancelled");
+        await expectError(
+          program.methods
+            .payExpense()
+            .accountsPartial({
+              propertyAccount: fx.property,
+              manager: manager.publicKey,
+              expense: expenseAt(1),
+              payeeUsdc,
+              rentVault: fx.rentVault,
+              rentVaultAta: fx.rentVaultAta,
+            })
+            .signers([manager])
+            .rpc(),
+          "ExpenseCancelled"
+        );
+      });
+
+      it("drops an unfunded expense from the amount still to withhold", async () => {
+        await record(2, 200);
+        await cancel(2, manager);
+
+        const property = await program.account.propertyAccount.fetch(fx.property);
+        assert.equal(property.expensesPayable.toNumber(), 0);
+        assert.equal(property.expenseFunds.toNumber(), 0);
+        await expectError(cancel(0, manager), "ExpenseAlreadyPaid");
+      });
+    });
+  });
+
+  describe("additional reward tokens", () => {
+    let fx: PropertyFixture;
+    let holder: web3.Keypair;
+    let rewardMint: web3.PublicKey;
+    let rewardVault: web3.PublicKey;
+    let rewardVaultAta: web3.PublicKey;
+    let holderReward: web3.PublicKey;
+    let holderUsdc: web3.PublicKey;
+
+    const claimRewards = () =>
+      program.methods
+        .claimRewards()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holder: holder.publicKey,
+          holderState: holderPda(holder.publicKey, fx.property),
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+          receiverUsdc: holderUsdc,
+          taxVaultAta: fx.taxVaultAta,
+          taxYearRecord: taxYearPda(fx.property, holder.publicKey),
+        })
+        .remainingAccounts([
+          { pubkey: rewardVaultAta, isWritable: true, isSigner: false },
+          { pubkey: rewardVault, isWritable: false, isSigner: false },
+          { pubkey: holderReward, isWritable: true, isSigner: false },
+        ])
+        .signers([holder])
+        .rpc();
+
+    before(async () => {
+      fx = await createProperty();
+      holder = await fundedWallet();
+      await mintFractions(fx, holder.publicKey, 1_000);
+
+      rewardMint = await createMint(provider.connection, payer, authority, null, 6);
+      rewardVault = pda("reward_vault", fx.property, rewardMint);
+      rewardVaultAta = await tokenAccount(rewardMint, rewardVault);
+      holderReward = await tokenAccount(rewardMint, holder.publicKey);
+      holderUsdc = await tokenAccount(fx.usdcMint, holder.publicKey);
+
+      await program.methods.addRewardMint().accountsPartial({ propertyAccount: fx.property, rewardMint }).rpc();
+      await program.methods
+        .depositReward(new BN(500))
+        .accountsPartial({
+          propertyAccount: fx.property,
+          payer: authority,
+          rewardMint,
+          payerToken: await tokenAccount(rewardMint, authority, 500),
+          rewardVault,
+          rewardVaultAta,
+        })
+        .rpc();
+      await depositRent(fx, 1_000);
+    });
+
+    it("rejects duplicate and unregistered reward mints", async () => {
+      await expectError(
+        program.methods.addRewardMint().accountsPartial({ propertyAccount: fx.property, rewardMint }).rpc(),
+        "RewardAlreadyRegistered"
+      );
+
+      const unregistered = await createMint(provider.connection, payer, authority, null, 6);
+      const vault = pda("reward_vault", fx.property, unregistered);
+      await expectError(
+        program.methods
+          .depositReward(new BN(1))
+          .accountsPartial({
+            propertyAccount: fx.property,
+            payer: authority,
+            rewardMint: unregistered,
+            payerToken: await tokenAccount(unregistered, authority, 1),
+            rewardVault: vault,
+            rewardVaultAta: await tokenAccount(unregistered, vault),
+          })
+          .rpc(),
+        "RewardNotRegistered"
+      );
+    });
+
+    it("pays USDC rent as stream 0 alongside each reward, once", async () => {
+      await claimRewards();
+      assert.equal((await tokenBalance(holderReward)).toString(), "500");
+      assert.equal((await tokenBalance(holderUsdc)).toString(), "1000");
+
+      await claimRewards();
+      assert.equal((await tokenBalance(holderReward)).toString(), "500");
+      assert.equal((await tokenBalance(holderUsdc)).toString(), "1000");
+    });
+
+    it("shares the rent index with claim_rent", async () => {
+      await depositRent(fx, 500);
+      await claimRent(fx, holder);
+      assert.equal((await tokenBalance(holderUsdc)).toString(), "1500");
+
+      await claimRewards();
+      assert.equal((await tokenBalance(holderUsdc)).toString(), "1500");
+      assert.equal((await tokenBalance(holderReward)).toString(), "500");
+    });
+
+    it("only pays rent to the holder's payout destination", async () => {
+      await depositRent(fx, 500);
+      await expectError(
+        program.methods
+          .claimRewards()
+          .accountsPartial({
+            propertyAccount: fx.property,
+            holder: holder.publicKey,
+            holderState: holderPda(holder.publicKey, fx.property),
+            rentVault: fx.rentVault,
+            rentVaultAta: fx.rentVaultAta,
+            receiverUsdc: fx.authorityUsdc,
+            taxVaultAta: fx.taxVaultAta,
+            taxYearRecord: taxYearPda(fx.property, holder.publicKey),
+          })
+          .remainingAccounts([])
+          .signers([holder])
+          .rpc(),
+        "InvalidPayoutDestination"
+      );
+    });
+  });
+
+  describe("rent distribution crank", () => {
+    let fx: PropertyFixture;
+    let alice: web3.Keypair;
+    let bob: web3.Keypair; // Subject to 20% withholding
+    let cranker: web3.Keypair;
+    let crankerUsdc: web3.PublicKey;
+
+    const crank = async (holders: web3.Keypair[], withTaxVault = true) =>
+      program.methods
+        .distributeRentBatch()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          cranker: cranker.publicKey,
+          crankerUsdc,
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+          taxVaultAta: withTaxVault ? fx.taxVaultAta : null,
+        })
+        .remainingAccounts(await crankAccounts(fx, holders))
+        .signers([cranker])
+        .rpc();
+
+    const fundCrank = (amount: number) =>
+      program.methods
+        .fundCrankRewards(new BN(amount))
+        .accountsPartial({
+          propertyAccount: fx.property,
+          funder: authority,
+          funderUsdc: fx.authorityUsdc,
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+        })
+        .rpc();
+
+    before(async () => {
+      const config = await ensureProtocolConfig();
+      fx = await createProperty();
+      alice = await fundedWallet();
+      bob = await fundedWallet();
+      cranker = await fundedWallet();
+      crankerUsdc = await tokenAccount(fx.usdcMint, cranker.publicKey);
+
+      await mintFractions(fx, alice.publicKey, 500);
+      await mintFractions(fx, bob.publicKey, 500);
+      await program.methods
+        .setTaxProfile(bob.publicKey, Array.from(Buffer.from("US")), 2_000)
+        .accountsPartial({
+          protocolConfig: config.protocolConfig,
+          complianceAuthority: authority,
+          taxProfile: pda("tax_profile", bob.publicKey),
+        })
+        .rpc();
+      await program.methods.setCrankReward(100).accountsPartial({ propertyAccount: fx.property }).rpc(); // 1%
+      await depositRent(fx, 2_000);
+    });
+
+    it("requires the tax vault to pay a holder subject to withholding", async () => {
+      await expectError(crank([bob], false), "InvalidTaxVault");
+    });
+
+    it("withholds tax and pays the crank reward from the sponsor's budget", async () => {
+      await fundCrank(15);
+      await crank([alice, bob]);
+
+      assert.equal((await tokenBalance(await tokenAccount(fx.usdcMint, alice.publicKey))).toString(), "1000");
+      assert.equal((await tokenBalance(await tokenAccount(fx.usdcMint, bob.publicKey))).toString(), "800");
+      assert.equal((await tokenBalance(fx.taxVaultAta)).toString(), "200");
+      // 1% of each 1000 payout, capped by the 15 funded
+      assert.equal((await tokenBalance(crankerUsdc)).toString(), "15");
+
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.crankFunds.toNumber(), 0);
+      assert.equal((await tokenBalance(fx.rentVaultAta)).toString(), "0");
+      const bobState = await program.account.holderState.fetch(holderPda(bob.publicKey, fx.property));
+      assert.equal(bobState.taxWithheld.toNumber(), 200);
+      const bobRecord = await program.account.taxYearRecord.fetch(taxYearPda(fx.property, bob.publicKey));
+      assert.equal(bobRecord.grossPaid.toNumber(), 1_000);
+      assert.equal(bobRecord.withheld.toNumber(), 200);
+      const aliceRecord = await program.account.taxYearRecord.fetch(taxYearPda(fx.property, alice.publicKey));
+      assert.equal(aliceRecord.grossPaid.toNumber(), 1_000);
+      assert.equal(aliceRecord.withheld.toNumber(), 0);
+    });
+
+    it("lets the authority withdraw unused crank funds", async () => {
+      await fundCrank(40);
+      await expectError(
+        program.methods
+          .withdrawCrankFunds(new BN(41))
+          .accountsPartial({
+            propertyAccount: fx.property,
+            destinationUsdc: fx.authorityUsdc,
+            rentVault: fx.rentVault,
+            rentVaultAta: fx.rentVaultAta,
+          })
+          .rpc(),
+        "InvalidAmount"
+      );
+      await program.methods
+        .withdrawCrankFunds(new BN(40))
+        .accountsPartial({
+          propertyAccount: fx.property,
+          destinationUsdc: fx.authorityUsdc,
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+        })
+        .rpc();
+
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.crankFunds.toNumber(), 0);
+      assert.equal((await tokenBalance(fx.rentVaultAta)).toString(), "0");
+    })
+    it("does not open tax year records for holders with nothing to pay", async () => {
+      const carol = await fundedWallet();
+      await mintFractions(fx, carol.publicKey, 100);
+      await crank([carol]);
+      assert.isNull(await provider.connection.getAccountInfo(taxYearPda(fx.property, carol.publicKey)));
+    });
+  });
+
+  describe("claim rent across properties", () => {
+    let first: PropertyFixture;
+    let second: PropertyFixture;
+    let holder: web3.Keypair;
+    let receiverUsdc: web3.PublicKey;
+
+    const tuple = (fx: PropertyFixture) => [
+      { pubkey: fx.property, isWritable: true, isSigner: false },
+      { pubkey: holderPda(holder.publicKey, fx.property), isWritable: true, isSigner: false },
+      { pubkey: fx.rentVaultAta, isWritable: true, isSigner: false },
+      { pubkey: fx.rentVault, isWritable: false, isSigner: false },
+      { pubkey: taxYearPda(fx.property, holder.publicKey), isWritable: true, isSigner: false },
+    ];
+
+    const claimMulti = (remaining: ReturnType<typeof tuple>) =>
+      program.methods
+        .claimRentMulti()
+        .accountsPartial({
+          holder: holder.publicKey,
+          receiverUsdc,
+          taxProfile: pda("tax_profile", holder.publicKey),
+        })
+        .remainingAccounts(remaining)
+        .signers([holder])
+        .rpc();
+
+    before(async () => {
+      first = await createProperty();
+      second = await createProperty({ usdcMint: first.usdcMint });
+      holder = await fundedWallet();
+      receiverUsdc = await tokenAccount(first.usdcMint, holder.publicKey);
+
+      await mintFractions(first, holder.publicKey, 1_000);
+      await mintFractions(second, holder.publicKey, 1_000);
+      await depositRent(first, 300);
+      await depositRent(second, 500);
+    });
+
+    it("rejects a partial tuple", async () => {
+      await expectError(claimMulti(tuple(first).slice(0, 4)), "InvalidRemainingAccounts");
+    });
+
+    it("pays every property into one account, once", async () => {
+      await claimMulti([...tuple(first), ...tuple(second)]);
+      assert.equal((await tokenBalance(receiverUsdc)).toString(), "800");
+      assert.equal((await tokenBalance(first.rentVaultAta)).toString(), "0");
+      assert.equal((await tokenBalance(second.rentVaultAta)).toString(), "0");
+
+      await claimMulti([...tuple(first), ...tuple(second)]);
+      assert.equal((await tokenBalance(receiverUsdc)).toString(), "800");
+
+      const record = await program.account.taxYearRecord.fetch(taxYearPda(second.property, holder.publicKey));
+      assert.equal(record.grossPaid.toNumber(), 500);
+    });
+  });
+
+  describe("tax withholding", () => {
+    let fx: PropertyFixture;
+    let taxed: web3.Keypair; // 15% withholding
+    let untaxed: web3.Keypair;
+
+    const claim = async (holder: web3.Keypair, withTaxVault: boolean) =>
+      program.methods
+        .claimRent()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holderWallet: holder.publicKey,
+          holderState: holderPda(holder.publicKey, fx.property),
+          payer: holder.publicKey,
+          receiverUsdc: await tokenAccount(fx.usdcMint, holder.publicKey),
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+          taxVaultAta: withTaxVault ? fx.taxVaultAta : null,
+          taxYearRecord: taxYearPda(fx.property, holder.publicKey),
+        })
+        .signers([holder])
+        .rpc();
+
+    before(async () => {
+      const config = await ensureProtocolConfig();
+      fx = await createProperty();
+      taxed = await fundedWallet();
+      untaxed = await fundedWallet();
+
+      await mintFractions(fx, taxed.publicKey, 500);
+      await mintFractions(fx, untaxed.publicKey, 500);
+      await program.methods
+        .setTaxProfile(taxed.publicKey, Array.from(Buffer.from("DE")), 1_500)
+        .accountsPartial({
+          protocolConfig: config.protocolConfig,
+          complianceAuthority: authority,
+          taxProfile: pda("tax_profile", taxed.publicKey),
+        })
+        .rpc();
+      await depositRent(fx, 4_000);
+    });
+
+    it("only needs the tax vault when tax is withheld", async () => {
+      await claim(untaxed, false);
+      assert.equal((await tokenBalance(await tokenAccount(fx.usdcMint, untaxed.publicKey))).toString(), "2000");
+
+      await expectError(claim(taxed, false), "InvalidTaxVault");
+    });
+
+    it("withholds into the tax vault and records the year on-chain", async () => {
+      await claim(taxed, true);
+      assert.equal((await tokenBalance(await tokenAccount(fx.usdcMint, taxed.publicKey))).toString(), "1700");
+      assert.equal((await tokenBalance(fx.taxVaultAta)).toString(), "300");
+
+      const record = await program.account.taxYearRecord.fetch(taxYearPda(fx.property, taxed.publicKey));
+      assert.equal(record.year, new Date().getUTCFullYear());
+      assert.equal(record.grossPaid.toNumber(), 2_000);
+      assert.equal(record.withheld.toNumber(), 300);
+
+      const untaxedRecord = await program.account.taxYearRecord.fetch(taxYearPda(fx.property, untaxed.publicKey));
+      assert.equal(untaxedRecord.grossPaid.toNumber(), 2_000);
+      assert.equal(untaxedRecord.withheld.toNumber(), 0);
+    });
+
+    it("rejects a record for another year", async () => {
+      await depositRent(fx, 1_000);
+      const lastYear = Buffer.alloc(2);
+      lastYear.writeUInt16LE(new Date().getUTCFullYear() - 1);
+      await expectError(
+        program.methods
+          .claimRent()
+          .accountsPartial({
+            propertyAccount: fx.property,
+            holderWallet: untaxed.publicKey,
+            holderState: holderPda(untaxed.publicKey, fx.property),
+            payer: untaxed.publicKey,
+            receiverUsdc: await tokenAccount(fx.usdcMint, untaxed.publicKey),
+            rentVault: fx.rentVault,
+            rentVaultAta: fx.rentVaultAta,
+            taxVaultAta: null,
+            taxYearRecord: pda("tax_year", fx.property, untaxed.publicKey, lastYear),
+          })
+          .signers([untaxed])
+          .rpc(),
+        "InvalidTaxYear"
+      );
+    });
+  });
+
+  describe("payout destinations and claim delegates", () => {
+    let fx: PropertyFixture;
+    let holder: web3.Keypair;
+    let delegate: web3.Keypair;
+    let treasuryUsdc: web3.PublicKey;
+
+    const claim = (payer: web3.Keypair, receiverUsdc: web3.PublicKey) =>
+      program.methods
+        .claimRent()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holderWallet: holder.publicKey,
+          holderState: holderPda(holder.publicKey, fx.property),
+          payer: payer.publicKey,
+          receiverUsdc,
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+          taxVaultAta: null,
+          taxYearRecord: taxYearPda(fx.property, holder.publicKey),
+        })
+        .signers([payer])
+        .rpc();
+
+    const settingsAccounts = () => ({
+      propertyAccount: fx.property,
+      holder: holder.publicKey,
+      holderState: holderPda(holder.publicKey, fx.property),
+    });
+
+    before(async () => {
+      fx = await createProperty();
+      holder = await fundedWallet();
+      delegate = await fundedWallet();
+      treasuryUsdc = await tokenAccount(fx.usdcMint, web3.Keypair.generate().publicKey);
+
+      await mintFractions(fx, holder.publicKey, 1_000);
+      await program.methods.setPayoutDestination(treasuryUsdc).accountsPartial(settingsAccounts()).signers([holder]).rpc();
+      await program.methods.setClaimDelegate(delegate.publicKey).accountsPartial(settingsAccounts()).signers([holder]).rpc();
+      await depositRent(fx, 1_000);
+    });
+
+    it("only pays to the payout destination", async () => {
+      await expectError(claim(holder, await tokenAccount(fx.usdcMint, holder.publicKey)), "InvalidPayoutDestination");
+      await expectError(claim(delegate, await tokenAccount(fx.usdcMint, delegate.publicKey)), "InvalidPayoutDestination");
+    });
+
+    it("lets the delegate trigger a claim but nobody else", async () => {
+      const stranger = await fundedWallet();
+      await expectError(claim(stranger, treasuryUsdc), "Unauthorized");
+
+      await claim(delegate, treasuryUsdc);
+      assert.equal((await tokenBalance(treasuryUsdc)).toString(), "1000");
+    });
+
+    it("stops honouring a revoked delegate", async () => {
+      await program.methods
+        .setClaimDelegate(web3.PublicKey.default)
+        .accountsPartial(settingsAccounts())
+        .signers([holder])
+        .rpc();
+      await depositRent(fx, 500);
+      await expectError(claim(delegate, treasuryUsdc), "Unauthorized");
+    });
+  });
+
+  describe("dividend reinvestment", () => {
+    let fx: PropertyFixture;
+    let alice: web3.Keypair; // DRIP holder
+    let bob: web3.Keypair;
+    let aliceFractions: web3.PublicKey;
+
+    const setOfferingPrice = (price: number) =>
+      program.methods.setOfferingPrice(new BN(price)).accountsPartial({ propertyAccount: fx.property }).rpc();
+
+    const reinvest = () =>
+      program.methods
+        .reinvestRent()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holderWallet: alice.publicKey,
+          holderState: holderPda(alice.publicKey, fx.property),
+          holderFractionAta: aliceFractions,
+          fractionMint: fx.fractionMint,
+          mintAuthority: fx.mintAuthority,
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+          sponsorUsdc: fx.authorityUsdc,
+          taxYearRecord: taxYearPda(fx.property, alice.publicKey),
+          caller: authority,
+        })
+        .rpc();
+
+    before(async () => {
+      fx = await createProperty();
+      alice = await fundedWallet();
+      bob = await fundedWallet();
+      aliceFractions = await mintFractions(fx, alice.publicKey, 500);
+      await mintFractions(fx, bob.publicKey, 500);
+
+      await program.methods
+        .setDrip(true, new BN(2))
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holder: alice.publicKey,
+          holderState: holderPda(alice.publicKey, fx.property),
+        })
+        .signers([alice])
+        .rpc();
+      await depositRent(fx, 2_000);
+    });
+
+    it("pays DRIP holders no cash", async () => {
+      await expectError(claimRent(fx, alice), "DripEnabled");
+
+      await program.methods
+        .distributeRentBatch()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          cranker: authority,
+          crankerUsdc: fx.authorityUsdc,
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+          taxVaultAta: null,
+        })
+        .remainingAccounts(await crankAccounts(fx, [alice, bob]))
+        .rpc();
+      assert.equal((await tokenBalance(await tokenAccount(fx.usdcMint, bob.publicKey))).toString(), "1000");
+      assert.equal((await tokenBalance(await tokenAccount(fx.usdcMint, alice.publicKey))).toString(), "0");
+      assert.equal((await tokenBalance(fx.rentVaultAta)).toString(), "1000");
+    });
+
+    it("only reinvests at or below the holder's maximum price", async () => {
+      await setOfferingPrice(3);
+      await expectError(reinvest(), "DripPriceTooHigh");
+
+      // 2 per whole fraction (6 decimals): 1000 of rent buys 500 fractions
+      await setOfferingPrice(2);
+      const sponsorBefore = await tokenBalance(fx.authorityUsdc);
+      await reinvest();
+
+      assert.equal((await tokenBalance(aliceFractions)).toString(), "500000500");
+      assert.equal(Number((await tokenBalance(fx.authorityUsdc)) - sponsorBefore), 1_000);
+      const state = await program.account.holderState.fetch(holderPda(alice.publicKey, fx.property));
+      assert.equal(state.balance.toNumber(), 500_000_500);
+      const record = await program.account.taxYearRecord.fetch(taxYearPda(fx.property, alice.publicKey));
+      assert.equal(record.grossPaid.toNumber(), 1_000);
+      assert.equal(record.withheld.toNumber(), 0);
+    });
+  });
+
+  describe("scrip dividends", () => {
+    let fx: PropertyFixture;
+    let alice: web3.Keypair;
+    let bob: web3.Keypair;
+    let aliceFractions: web3.PublicKey;
+    let bobFractions: web3.PublicKey;
+
+    const declare = (amount: number) =>
+      program.methods.declareScripDividend(new BN(amount)).accountsPartial({ propertyAccount: fx.property }).rpc();
+
+    const claimScrip = (holder: web3.Keypair, holderFractionAta: web3.PublicKey) =>
+      program.methods
+        .claimScrip()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holder: holder.publicKey,
+          holderState: holderPda(holder.publicKey, fx.property),
+          holderFractionAta,
+          fractionMint: fx.fractionMint,
+          mintAuthority: fx.mintAuthority,
+        })
+        .signers([holder])
+        .rpc();
+
+    before(async () => {
+      fx = await createProperty({ totalFractions: 1_500 });
+      alice = await fundedWallet();
+      bob = await fundedWallet();
+      aliceFractions = await mintFractions(fx, alice.publicKey, 600);
+      bobFractions = await mintFractions(fx, bob.publicKey, 400);
+    });
+
+    it("reserves declared scrip against total_fractions", async () => {
+      await expectError(declare(501), "ExceedsTotalFractions");
+      await declare(100);
+
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.scripReserved.toNumber(), 100);
+      assert.equal(property.mintedFractions.toNumber(), 1_000);
+    });
+
+    it("mints each holder's pro-rata scrip once", async () => {
+      await claimScrip(alice, aliceFractions);
+      assert.equal((await tokenBalance(aliceFractions)).toString(), "660");
+      await expectError(claimScrip(alice, aliceFractions), "NoPendingRewards");
+
+      await claimScrip(bob, bobFractions);
+      assert.equal((await tokenBalance(bobFractions)).toString(), "440");
+
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.equal(property.scripReserved.toNumber(), 0);
+      assert.equal(property.mintedFractions.toNumber(), 1_100);
+      assert.equal(property.trackedBalance.toNumber(), 1_100);
+    });
+
+    it("divides later rent over the diluted supply", async () => {
+      await depositRent(fx, 1_100);
+      assert.equal((await tokenBalance(await claimRent(fx, alice))).toString(), "660");
+      assert.equal((await tokenBalance(await claimRent(fx, bob))).toString(), "440");
+    });
+  });
+
+  describe("unclaimed rent escheatment", () => {
+    let fx: PropertyFixture;
+    let alice: web3.Keypair; // 10% withholding
+    let bob: web3.Keypair;
+    let cranker: web3.Keypair;
+
+    const escheat = (holder: web3.Keypair) =>
+      program.methods
+        .escheatUnclaimed()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holderWallet: holder.publicKey,
+          holderState: holderPda(holder.publicKey, fx.property),
+          escheatRecord: pda("escheat", fx.property, holder.publicKey),
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+          escheatVault: fx.escheatVault,
+          escheatVaultAta: fx.escheatVaultAta,
+        })
+        .rpc();
+
+    const reclaim = async (holder: web3.Keypair) =>
+      program.methods
+        .reclaimEscheated()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holder: holder.publicKey,
+          holderState: holderPda(holder.publicKey, fx.property),
+          escheatRecord: pda("escheat", fx.property, holder.publicKey),
+          escheatVault: fx.escheatVault,
+          escheatVaultAta: fx.escheatVaultAta,
+          receiverUsdc: await tokenAccount(fx.usdcMint, holder.publicKey),
+          taxVaultAta: fx.taxVaultAta,
+          taxYearRecord: taxYearPda(fx.property, holder.publicKey),
+        })
+        .signers([holder])
+        .rpc();
+
+    const crank = async (holder: web3.Keypair) =>
+      program.methods
+        .distributeRentBatch()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          cranker: cranker.publicKey,
+          crankerUsdc: await tokenAccount(fx.usdcMint, cranker.publicKey),
+          rentVault: fx.rentVault,
+          rentVaultAta: fx.rentVaultAta,
+          taxVaultAta: null,
+        })
+        .remainingAccounts(await crankAccounts(fx, [holder]))
+        .signers([cranker])
+        .rpc();
+
+    before(async () => {
+      const config = await ensureProtocolConfig();
+      fx = await createProperty();
+      alice = await fundedWallet();
+      bob = await fundedWallet();
+      cranker = await fundedWallet();
+
+      await mintFractions(fx, alice.publicKey, 500);
+      await mintFractions(fx, bob.publicKey, 500);
+      await program.methods
+        .setTaxProfile(alice.publicKey, Array.from(Buffer.from("FR")), 1_000)
+        .accountsPartial({
+          protocolConfig: config.protocolConfig,
+          complianceAuthority: authority,
+          taxProfile: pda("tax_profile", alice.publicKey),
+        })
+        .rpc();
+      await program.methods
+        .setEscheatPolicy(new BN(2), new BN(0))
+        .accountsPartial({ propertyAccount: fx.property })
+        .rpc();
+      await depositRent(fx, 2_000);
+      await sleep(3_000);
+    });
+
+    it("withholds tax when a holder reclaims escheated rent", async () => {
+      await escheat(alice);
+      assert.equal((await tokenBalance(fx.escheatVaultAta)).toString(), "1000");
+
+      await reclaim(alice);
+      assert.equal((await tokenBalance(await tokenAccount(fx.usdcMint, alice.publicKey))).toString(), "900");
+      assert.equal((await tokenBalance(fx.taxVaultAta)).toString(), "100");
+      assert.equal((await tokenBalance(fx.escheatVaultAta)).toString(), "0");
+
+      const record = await program.account.taxYearRecord.fetch(taxYearPda(fx.property, alice.publicKey));
+      assert.equal(record.grossPaid.toNumber(), 1_000);
+      assert.equal(record.withheld.toNumber(), 100);
+      await expectError(reclaim(alice), "NothingToReclaim");
+    });
+
+    it("counts only holder-signed actions as activity", async () => {
+      // A crank payout does not make bob active
+      await crank(bob);
+      assert.equal((await tokenBalance(await tokenAccount(fx.usdcMint, bob.publicKey))).toString(), "1000");
+
+      await depositRent(fx, 2_000);
+      await escheat(bob);
+      assert.equal((await tokenBalance(fx.escheatVaultAta)).toString(), "1000");
+
+      // Alice's reclaim was signed by her
+      await expectError(escheat(alice), "HolderNotDormant");
+    });
+  });
+
+  describe("supply reconciliation", () => {
+    let fx: PropertyFixture;
+    let alice: web3.Keypair;
+    let bob: web3.Keypair;
+    let aliceFractions: web3.PublicKey;
+
+    // Optionally syncs alice, the only holder who burned outside the program
+    const resync = (syncAlice: boolean) =>
+      program.methods
+        .resyncSupply()
+        .accountsPartial({ propertyAccount: fx.property, fractionMint: fx.fractionMint })
+        .remainingAccounts(
+          syncAlice
+            ? [
+                { pubkey: holderPda(alice.publicKey, fx.property), isWritable: true, isSigner: false },
+                { pubkey: aliceFractions, isWritable: false, isSigner: false },
+              ]
+            : []
+        )
+        .rpc();
+
+    before(async () => {
+      fx = await createProperty();
+      alice = await fundedWallet();
+      bob = await fundedWallet();
+      aliceFractions = await mintFractions(fx, alice.publicKey, 600);
+      await mintFractions(fx, bob.publicKey, 400);
+
+      // Burned with the token program directly, so the property never sees it
+      await burn(provider.connection, payer, aliceFractions, fx.fractionMint, alice, 100);
+    });
+
+    it("lets only the authority halt distributions on a mismatch", async () => {
+      const reconcile = (signer?: web3.Keypair) =>
+        program.methods
+          .reconcileSupply()
+          .accountsPartial({
+            propertyAccount: fx.property,
+            authority: signer ? signer.publicKey : authority,
+            fractionMint: fx.fractionMint,
+          })
+          .signers(signer ? [signer] : [])
+          .rpc();
+
+      await expectError(reconcile(bob), "ConstraintHasOne");
+      await reconcile();
+      assert.isTrue((await program.account.propertyAccount.fetch(fx.property)).supplyHalted);
+
+      // Rent would still be divided over the 1000 minted, paying alice on burned fractions
+      await expectError(depositRent(fx, 1_000), "SupplyMismatch");
+    });
+
+    it("lets the authority adopt the mint supply once balances are synced", async () => {
+      await expectError(resync(false), "UntrackedSupplyExceeded");
+      await resync(true);
+
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      assert.isFalse(property.supplyHalted);
+      assert.equal(property.mintedFractions.toNumber(), 900);
+      assert.equal(property.trackedBalance.toNumber(), 900);
+
+      await depositRent(fx, 900);
+      assert.equal((await tokenBalance(await claimRent(fx, alice))).toString(), "500");
+      assert.equal((await tokenBalance(await claimRent(fx, bob))).toString(), "400");
+    });
+  });
+
+  describe("holding period forfeiture", () => {
+    let fx: PropertyFixture;
+    let carol: web3.Keypair; // Long-term holder
+    let bob: web3.Keypair; // Long-term holder who sells
+    let alice: web3.Keypair; // Buys just before a deposit
+
+    before(async () => {
+      fx = await createProperty();
+      carol = await fundedWallet();
+      bob = await fundedWallet();
+      alice = await fundedWallet();
+      await mintFractions(fx, carol.publicKey, 500);
+      await mintFractions(fx, bob.publicKey, 500);
+      await program.methods.setHoldingPeriod(new BN(4)).accountsPartial({ propertyAccount: fx.property }).rpc();
+      await sleep(5_000);
+    });
+
+    it("rejects a negative holding period", async () => {
+      await expectError(
+        program.methods.setHoldingPeriod(new BN(-1)).accountsPartial({ propertyAccount: fx.property }).rpc(),
+        "InvalidAmount"
+      );
+    });
+
+    it("redistributes forfeited rent to holders other than the counterparties", async () => {
+      await transferFractions(fx, bob, alice, 250);
+      await depositRent(fx, 1_000);
+      // Alice settles her 250 well inside the holding period while trading with bob
+      await transferFractions(fx, bob, alice, 250);
+
+      const property = await program.account.propertyAccount.fetch(fx.property);
+      const forfeited = property.totalRentForfeited.div(new BN(1_000_000_000)).toNumber();
+      assert.isAbove(forfeited, 0);
+
+      await sleep(6_000);
+      const carolPaid = Number(await tokenBalance(await claimRent(fx, carol)));
+      const bobPaid = Number(await tokenBalance(await claimRent(fx, bob)));
+      const alicePaid = Number(await tokenBalance(await claimRent(fx, alice)));
+
+      // Bob's share is settled before the forfeit is credited, so it all goes to carol
+      assert.equal(bobPaid, 250);
+      assert.isAtLeast(carolPaid, 500 + forfeited - 1);
+      assert.isAtMost(alicePaid, 250 - forfeited + 1);
+      assert.isAtLeast(carolPaid + bobPaid + alicePaid, 999);
+      assert.isAtMost(Number(await tokenBalance(fx.rentVaultAta)), 1);
+    });
+
+    const forfeitedSoFar = async () =>
+      (await program.account.propertyAccount.fetch(fx.property)).totalRentForfeited.div(new BN(1_000_000_000)).toNumber();
+
+    it("forfeits rent credited inside the window when claimed after it", async () => {
+      const dave = await fundedWallet();
+      await transferFractions(fx, carol, dave, 100);
+      await depositRent(fx, 100_000);
+      await sleep(6_000);
+
+      const before = await forfeitedSoFar();
+      const davePaid = Number(await tokenBalance(await claimRent(fx, dave)));
+      const forfeited = (await forfeitedSoFar()) - before;
+
+      // Dave's pro rata share is 10_000; waiting out the period doesn't restore it
+      assert.isAbove(forfeited, 0);
+      assert.isBelow(davePaid, 10_000);
+      assert.approximately(davePaid + forfeited, 10_000, 2);
+    });
+
+    it("forfeits the same when a third party syncs the holder inside the window", async () => {
+      const erin = await fundedWallet();
+      const frank = await fundedWallet();
+      // One transaction, so both acquire at the same time
+      const tx = new web3.Transaction().add(
+        await transferFractionsIx(fx, carol, erin, 1),
+        await transferFractionsIx(fx, carol, frank, 1)
+      );
+      await provider.sendAndConfirm(tx, [carol, erin, frank]);
+      await depositRent(fx, 1_000_000);
+
+      const before = await forfeitedSoFar();
+      await program.methods
+        .syncHolder()
+        .accountsPartial({
+          propertyAccount: fx.property,
+          holderWallet: erin.publicKey,
+          holderState: holderPda(erin.publicKey, fx.property),
+          holderFractionAta: await tokenAccount(fx.fractionMint, erin.publicKey),
+        })
+        .rpc();
+      assert.isAbove((await forfeitedSoFar()) - before, 0);
+
+      await sleep(6_000);
+      const erinPaid = Number(await tokenBalance(await claimRent(fx, erin)));
+      const frankPaid = Number(await tokenBalance(await claimRent(fx, frank)));
+
+      // Each share is 1_000; both forfeit by the deposit time, not the settlement time.
+      // The slack covers each receiving a sliver of the other's forfeit.
+      assert.isBelow(erinPaid, 1_000);
+      assert.approximately(erinPaid, frankPaid, 2);
+    });
+  });
 });
@@ -0,0 +1,276 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, web3, BN } from "@coral-xyz/anchor";
+import { Rwa } from "../target/types/rwa";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  getAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+// Shared fixtures for the test suites. Nothing in here registers tests.
+
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+
+export const program = anchor.workspace.Rwa as Program<Rwa>;
+export const authority = provider.wallet.publicKey;
+export const payer = (provider.wallet as anchor.Wallet).payer;
+
+export const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+export const u64 = (n: number | BN) => new BN(n).toArrayLike(Buffer, "le", 8);
+
+export function pda(...seeds: (string | Buffer | web3.PublicKey)[]): web3.PublicKey {
+  const bytes = seeds.map((s) =>
+    typeof s === "string" ? Buffer.from(s) : s instanceof web3.PublicKey ? s.toBuffer() : s
+  );
+  return web3.PublicKey.findProgramAddressSync(bytes, program.programId)[0];
+}
+
+export const holderPda = (wallet: web3.PublicKey, property: web3.PublicKey) => pda("holder", wallet, property);
+
+/** The holder's tax year record for the current UTC year. */
+export function taxYearPda(property: web3.PublicKey, wallet: web3.PublicKey): web3.PublicKey {
+  const year = Buffer.alloc(2);
+  year.writeUInt16LE(new Date().getUTCFullYear());
+  return pda("tax_year", property, wallet, year);
+}
+
+export async function fundedWallet(sol = 10): Promise<web3.Keypair> {
+  const wallet = web3.Keypair.generate();
+  await provider.connection.confirmTransaction(
+    await provider.connection.requestAirdrop(wallet.publicKey, sol * web3.LAMPORTS_PER_SOL)
+  );
+  return wallet;
+}
+
+/** Token account of `owner` for `mint`, topped up by `amount` (authority must be the mint authority). */
+export async function tokenAccount(mint: web3.PublicKey, owner: web3.PublicKey, amount = 0): Promise<web3.PublicKey> {
+  const account = await getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, owner, true);
+  if (amount > 0) {
+    await mintTo(provider.connection, payer, mint, account.address, authority, amount);
+  }
+  return account.address;
+}
+
+export async function tokenBalance(address: web3.PublicKey): Promise<bigint> {
+  return (await getAccount(provider.connection, address)).amount;
+}
+
+/** Assert that `promise` rejects with an error mentioning `code`. */
+export async function expectError(promise: Promise<unknown>, code: string) {
+  try {
+    await promise;
+  } catch (err) {
+    // Views surface failures as simulation errors that only carry the logs
+    const { logs, simulationResponse } = err as { logs?: string[]; simulationResponse?: { logs?: string[] } };
+    const allLogs = logs ?? simulationResponse?.logs ?? [];
+    assert.include(`${err} ${allLogs.join("\n")}`, code);
+    return;
+  }
+  assert.fail(`expected ${code}`);
+}
+
+export interface PropertyFixture {
+  property: web3.PublicKey;
+  fractionMint: web3.PublicKey;
+  usdcMint: web3.PublicKey;
+  mintAuthority: web3.PublicKey;
+  rentVault: web3.PublicKey;
+  rentVaultAta: web3.PublicKey;
+  reserveVault: web3.PublicKey;
+  reserveVaultAta: web3.PublicKey;
+  taxVault: web3.PublicKey;
+  taxVaultAta: web3.PublicKey;
+  escheatVault: web3.PublicKey;
+  escheatVaultAta: web3.PublicKey;
+  authorityUsdc: web3.PublicKey; // Funds deposits and receives sponsor fees
+}
+
+/** Create a property with 6-decimal fractions and every vault ATA it uses. */
+export async function createProperty(
+  opts: { usdcMint?: web3.PublicKey; totalFractions?: number } = {}
+): Promise<PropertyFixture> {
+  const propertyAccount = web3.Keypair.generate();
+  const fractionMint = web3.Keypair.generate();
+  const property = propertyAccount.publicKey;
+
+  const usdcMint = opts.usdcMint ?? (await createMint(provider.connection, payer, authority, null, 6));
+  const nftMint = await createMint(provider.connection, payer, authority, null, 0);
+
+  const mintAuthority = pda("fraction_authority", property);
+  const rentVault = pda("rent_vault", fractionMint.publicKey);
+  const nftVault = pda("nft_vault", nftMint);
+  const reserveVault = pda("reserve_vault", property);
+  const taxVault = pda("tax_vault", property);
+  const escheatVault = pda("escheat_vault", property);
+
+  const rentVaultAta = await tokenAccount(usdcMint, rentVault);
+  const nftVaultAta = await tokenAccount(nftMint, nftVault);
+
+  const uri = Buffer.alloc(200);
+  Buffer.from("ipfs://fixture").copy(uri);
+
+  await program.methods
+    .initializeProperty(Array.from(uri), new BN(opts.totalFractions ?? 1_000_000_000), 6)
+    .accountsPartial({
+      propertyAccount: property,
+      authority,
+      nftMint,
+      fractionMint: fractionMint.publicKey,
+      mintAuthority,
+      rentVault,
+      nftVault,
+      rentVaultAta,
+      nftVaultAta,
+      usdcMint,
+    })
+    .signers([propertyAccount, fractionMint])
+    .rpc();
+
+  return {
+    property,
+    fractionMint: fractionMint.publicKey,
+    usdcMint,
+    mintAuthority,
+    rentVault,
+    rentVaultAta,
+    reserveVault,
+    reserveVaultAta: await tokenAccount(usdcMint, reserveVault),
+    taxVault,
+    taxVaultAta: await tokenAccount(usdcMint, taxVault),
+    escheatVault,
+    escheatVaultAta: await tokenAccount(usdcMint, escheatVault),
+    authorityUsdc: await tokenAccount(usdcMint, authority, 1_000_000_000_000),
+  };
+}
+
+/** Mint fractions to `owner` through the program; returns their fraction ATA. */
+export async function mintFractions(fx: PropertyFixture, owner: web3.PublicKey, amount: number): Promise<web3.PublicKey> {
+  const destination = await tokenAccount(fx.fractionMint, owner);
+  await program.methods
+    .mintFractions(new BN(amount))
+    .accountsPartial({
+      propertyAccount: fx.property,
+      fractionMint: fx.fractionMint,
+      mintAuthority: fx.mintAuthority,
+      destination,
+      destinationHolder: holderPda(owner, fx.property),
+    })
+    .rpc();
+  return destination;
+}
+
+/** Deposit rent from the authority; returns the epoch opened for it. */
+export async function depositRent(fx: PropertyFixture, amount: number, recordDate = 0): Promise<web3.PublicKey> {
+  const { epochCount } = await program.account.propertyAccount.fetch(fx.property);
+  const distributionEpoch = pda("epoch", fx.property, u64(epochCount));
+  await program.methods
+    .depositRent(new BN(amount), new BN(recordDate), Array.from(Buffer.alloc(32)))
+    .accountsPartial({
+      propertyAccount: fx.property,
+      payer: authority,
+      rentPayer: null,
+      distributionEpoch,
+      payerUsdc: fx.authorityUsdc,
+      rentVault: fx.rentVault,
+      rentVaultAta: fx.rentVaultAta,
+      reserveVault: fx.reserveVault,
+      reserveVaultAta: fx.reserveVaultAta,
+      sponsorUsdc: fx.authorityUsdc,
+    })
+    .rpc();
+  return distributionEpoch;
+}
+
+/** `holder` claims its rent into its own USDC account, which is returned. */
+export async function claimRent(fx: PropertyFixture, holder: web3.Keypair): Promise<web3.PublicKey> {
+  const receiverUsdc = await tokenAccount(fx.usdcMint, holder.publicKey);
+  await program.methods
+    .claimRent()
+    .accountsPartial({
+      propertyAccount: fx.property,
+      holderWallet: holder.publicKey,
+      holderState: holderPda(holder.publicKey, fx.property),
+      payer: holder.publicKey,
+      receiverUsdc,
+      rentVault: fx.rentVault,
+      rentVaultAta: fx.rentVaultAta,
+      taxVaultAta: fx.taxVaultAta,
+      taxYearRecord: taxYearPda(fx.property, holder.publicKey),
+    })
+    .signers([holder])
+    .rpc();
+  return receiverUsdc;
+}
+
+/** `from` transfers fractions to `to` through the program; both sign. */
+export async function transferFractions(fx: PropertyFixture, from: web3.Keypair, to: web3.Keypair, amount: number) {
+  const tx = new web3.Transaction().add(await transferFractionsIx(fx, from, to, amount));
+  return provider.sendAndConfirm(tx, [from, to]);
+}
+
+/** The transfer_fractions instruction, for batching several transfers in one transaction. */
+export async function transferFractionsIx(
+  fx: PropertyFixture,
+  from: web3.Keypair,
+  to: web3.Keypair,
+  amount: number
+): Promise<web3.TransactionInstruction> {
+  return program.methods
+    .transferFractions(new BN(amount))
+    .accountsPartial({
+      propertyAccount: fx.property,
+      sourceHolder: holderPda(from.publicKey, fx.property),
+      destHolder: holderPda(to.publicKey, fx.property),
+      sourceFractionAta: await tokenAccount(fx.fractionMint, from.publicKey),
+      destFractionAta: await tokenAccount(fx.fractionMint, to.publicKey),
+      sourceOwner: from.publicKey,
+      destOwner: to.publicKey,
+    })
+    .instruction();
+}
+
+/** Remaining accounts for distribute_rent_batch: [holder_state, usdc, tax_profile, tax_year] per holder. */
+export async function crankAccounts(fx: PropertyFixture, holders: web3.Keypair[]): Promise<web3.AccountMeta[]> {
+  const accounts: web3.AccountMeta[] = [];
+  for (const holder of holders) {
+    accounts.push(
+      { pubkey: holderPda(holder.publicKey, fx.property), isWritable: true, isSigner: false },
+      { pubkey: await tokenAccount(fx.usdcMint, holder.publicKey), isWritable: true, isSigner: false },
+      { pubkey: pda("tax_profile", holder.publicKey), isWritable: false, isSigner: false },
+      { pubkey: taxYearPda(fx.property, holder.publicKey), isWritable: true, isSigner: false }
+    );
+  }
+  return accounts;
+}
+
+export interface ProtocolFixture {
+  protocolConfig: web3.PublicKey;
+  treasuryUsdc: web3.PublicKey;
+  usdcMint: web3.PublicKey; // Mint of the treasury; trades must settle in it
+}
+
+/** The protocol config singleton, created fee-free by the upgrade authority if missing. */
+export async function ensureProtocolConfig(): Promise<ProtocolFixture> {
+  const protocolConfig = pda("protocol_config");
+  const existing = await program.account.protocolConfig.fetchNullable(protocolConfig);
+  if (existing) {
+    const treasury = await getAccount(provider.connection, existing.treasury);
+    return { protocolConfig, treasuryUsdc: existing.treasury, usdcMint: treasury.mint };
+  }
+
+  const usdcMint = await createMint(provider.connection, payer, authority, null, 6);
+  const treasuryUsdc = await tokenAccount(usdcMint, web3.Keypair.generate().publicKey);
+  const programData = web3.PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  )[0];
+  await program.methods
+    .initializeProtocolConfig(0, new BN(0))
+    .accountsPartial({ protocolConfig, admin: authority, treasuryUsdc, program: program.programId, programData })
+    .rpc();
+  return { protocolConfig, treasuryUsdc, usdcMint };
+}